@@ -0,0 +1,393 @@
+//! Integration with axum and tower
+//!
+//! * [`webhook_route`] serves an axum route that verifies webhook deliveries with a
+//!   [`WebhookVerifier`] and passes them to a handler.
+//! * [`TrackEventsLayer`] is a tower layer that queues events on an [`EventBuffer`]
+//!   for requests matching configured routes.
+//!
+//! Requires the `axum` feature.
+
+use crate::webhook::{HandlerResult, WebhookVerifier};
+use crate::event_buffer::EventBuffer;
+use crate::{Error, EventData};
+use ::axum::body::Bytes;
+use ::axum::extract::MatchedPath;
+use ::axum::http::request::Parts;
+use ::axum::http::{HeaderMap, Method, Request, Response, StatusCode};
+use ::axum::routing::{post, MethodRouter};
+use futures::future::BoxFuture;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+use tracing::warn;
+
+/// Create a POST route that verifies webhooks and passes their JSON body to `handler`
+///
+/// The signature is read from the verifier's header.
+///
+/// Responds with:
+/// * `200 OK` once the handler has completed
+/// * `401 Unauthorized` if the signature is missing or does not verify
+/// * `400 Bad Request` if the body is not valid JSON
+/// * `500 Internal Server Error` if the handler fails, so the delivery is retried
+///
+/// # Example
+/// ```no_run
+/// use axum::Router;
+/// use bento::webhook::{SignatureScheme, WebhookVerifier};
+///
+/// let verifier = WebhookVerifier::new("whsec_...", "X-Webhook-Signature", SignatureScheme::Body);
+/// let route = bento::axum::webhook_route(verifier, |payload| async move {
+///     println!("received {}", payload);
+///     Ok(())
+/// });
+///
+/// let app: Router = Router::new().route("/webhooks/bento", route);
+/// ```
+pub fn webhook_route<S, F, Fut>(verifier: WebhookVerifier, handler: F) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+    F: Fn(serde_json::Value) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = HandlerResult> + Send + 'static,
+{
+    let handler = Arc::new(handler);
+    post(move |headers: HeaderMap, body: Bytes| {
+        let verifier = verifier.clone();
+        let handler = Arc::clone(&handler);
+        async move {
+            let signature = headers.get(verifier.header_name())
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default();
+
+            let result = match verifier.verify_and_parse(signature, &body) {
+                Ok(payload) => handler(payload).await.map_err(Error::WebhookHandler),
+                Err(err) => Err(err),
+            };
+            match result {
+                Ok(()) => StatusCode::OK,
+                Err(err) => {
+                    warn!(error = %err, "rejected webhook delivery");
+                    match err {
+                        Error::InvalidSignature(_) => StatusCode::UNAUTHORIZED,
+                        Error::Json(_) => StatusCode::BAD_REQUEST,
+                        _ => StatusCode::INTERNAL_SERVER_ERROR,
+                    }
+                }
+            }
+        }
+    })
+}
+
+type EventBuilder = Arc<dyn Fn(&Parts) -> Option<EventData> + Send + Sync>;
+
+/// A route to track and how to build its event
+#[derive(Clone)]
+struct TrackRule {
+    method: Method,
+    pattern: String,
+    build: EventBuilder,
+}
+
+impl TrackRule {
+    /// Matches the route template when axum provides one, otherwise the request path.
+    fn matches(&self, parts: &Parts) -> bool {
+        if parts.method != self.method {
+            return false;
+        }
+
+        let paths = [
+            parts.extensions.get::<MatchedPath>().map(MatchedPath::as_str),
+            Some(parts.uri.path()),
+        ];
+        paths.into_iter().flatten().any(|path| match self.pattern.strip_suffix("/*") {
+            Some(prefix) => path == prefix || path.strip_prefix(prefix).is_some_and(|rest| rest.starts_with('/')),
+            None => path == self.pattern,
+        })
+    }
+}
+
+/// Tower layer that tracks Bento events for matching requests
+///
+/// Each rule pairs a method and path pattern with a function that builds the event
+/// from the request, typically reading the user's email from a header or from an
+/// extension set by authentication middleware. The function returns `None` to skip
+/// a request, e.g. for anonymous visitors.
+///
+/// Patterns are matched against the axum route template (`/posts/:id`) when the
+/// layer is added with `Router::layer`, and against the request path otherwise. A
+/// pattern ending in `/*` matches everything below it.
+///
+/// Events are queued on an [`EventBuffer`] once the inner service has responded with
+/// a status below 400, so they are sent in batches by a single background task
+/// rather than one request per HTTP request. Events that cannot be queued because
+/// the buffer is full or closed are logged and dropped without affecting the
+/// response. Flush or close the buffer on shutdown to send the remaining events.
+///
+/// # Example
+/// ```no_run
+/// use axum::http::Method;
+/// use axum::routing::{get, post};
+/// use axum::Router;
+/// use bento::axum::TrackEventsLayer;
+/// use bento::event_buffer::EventBuffer;
+/// use bento::{Client, EventData};
+/// use std::sync::Arc;
+///
+/// fn user_email(parts: &axum::http::request::Parts) -> Option<bento::EmailAddress> {
+///     parts.headers.get("x-user-email")?.to_str().ok()?.parse().ok()
+/// }
+///
+/// # fn example(client: Client) -> bento::Result<()> {
+/// let buffer = Arc::new(EventBuffer::builder(client).build()?);
+/// let tracking = TrackEventsLayer::new(Arc::clone(&buffer))
+///     .track(Method::GET, "/pricing", |parts| {
+///         Some(EventData {
+///             event_type: "$view".into(),
+///             email: user_email(parts)?,
+///             fields: None,
+///             details: None,
+///         })
+///     })
+///     .track(Method::POST, "/signup", |parts| {
+///         Some(EventData {
+///             event_type: "$signup".into(),
+///             email: user_email(parts)?,
+///             fields: None,
+///             details: None,
+///         })
+///     });
+///
+/// let app: Router = Router::new()
+///     .route("/pricing", get(|| async { "pricing" }))
+///     .route("/signup", post(|| async { "welcome" }))
+///     .layer(tracking);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct TrackEventsLayer {
+    buffer: Arc<EventBuffer>,
+    rules: Arc<Vec<TrackRule>>,
+}
+
+impl fmt::Debug for TrackEventsLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let routes: Vec<String> = self.rules.iter()
+            .map(|rule| format!("{} {}", rule.method, rule.pattern))
+            .collect();
+        f.debug_struct("TrackEventsLayer").field("routes", &routes).finish()
+    }
+}
+
+impl TrackEventsLayer {
+    /// Create a layer without any routes that queues events on `buffer`
+    pub fn new(buffer: Arc<EventBuffer>) -> Self {
+        Self {
+            buffer,
+            rules: Arc::new(Vec::new()),
+        }
+    }
+
+    /// Track requests with the given method and path pattern
+    pub fn track<F>(mut self, method: Method, pattern: impl Into<String>, build: F) -> Self
+    where
+        F: Fn(&Parts) -> Option<EventData> + Send + Sync + 'static,
+    {
+        Arc::make_mut(&mut self.rules).push(TrackRule {
+            method,
+            pattern: pattern.into(),
+            build: Arc::new(build),
+        });
+        self
+    }
+}
+
+impl<S> Layer<S> for TrackEventsLayer {
+    type Service = TrackEvents<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TrackEvents {
+            inner,
+            buffer: Arc::clone(&self.buffer),
+            rules: Arc::clone(&self.rules),
+        }
+    }
+}
+
+/// Service created by [`TrackEventsLayer`]
+#[derive(Clone)]
+pub struct TrackEvents<S> {
+    inner: S,
+    buffer: Arc<EventBuffer>,
+    rules: Arc<Vec<TrackRule>>,
+}
+
+impl<S: fmt::Debug> fmt::Debug for TrackEvents<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrackEvents").field("inner", &self.inner).finish_non_exhaustive()
+    }
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for TrackEvents<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, std::result::Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<std::result::Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let (parts, body) = request.into_parts();
+        let events: Vec<EventData> = self.rules.iter()
+            .filter(|rule| rule.matches(&parts))
+            .filter_map(|rule| (rule.build)(&parts))
+            .collect();
+
+        let response = self.inner.call(Request::from_parts(parts, body));
+        let buffer = Arc::clone(&self.buffer);
+
+        Box::pin(async move {
+            let response = response.await?;
+
+            if response.status().as_u16() < 400 {
+                for event in events {
+                    if let Err(err) = buffer.push(event) {
+                        warn!(error = %err, "failed to queue request event");
+                    }
+                }
+            }
+
+            Ok(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::FakeBento;
+    use crate::webhook::SignatureScheme;
+    use ::axum::body::Body;
+    use ::axum::routing::get;
+    use ::axum::Router;
+    use tower::ServiceExt;
+
+    fn page_view(parts: &Parts) -> Option<EventData> {
+        Some(EventData {
+            event_type: "$view".into(),
+            email: parts.headers.get("x-user-email")?.to_str().ok()?.parse().ok()?,
+            fields: None,
+            details: Some([("path".to_string(), parts.uri.path().into())].into()),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_webhook_route() {
+        let verifier = WebhookVerifier::new("secret", "X-Webhook-Signature", SignatureScheme::Timestamped);
+        let app: Router = Router::new().route(
+            "/webhooks",
+            webhook_route(verifier.clone(), |payload| async move {
+                match payload["type"].as_str() {
+                    Some("fail") => Err("handler failed".into()),
+                    _ => Ok(()),
+                }
+            }),
+        );
+        let sign = |body: &str| verifier.sign(time::OffsetDateTime::now_utc().unix_timestamp(), body.as_bytes());
+
+        let body = r#"{"type":"ping"}"#;
+        let request = Request::post("/webhooks")
+            .header("X-Webhook-Signature", sign(body))
+            .body(Body::from(body))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let request = Request::post("/webhooks").body(Body::from(body)).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let request = Request::post("/webhooks")
+            .header("X-Other-Signature", sign(body))
+            .body(Body::from(body))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let request = Request::post("/webhooks")
+            .header("X-Webhook-Signature", sign("not json"))
+            .body(Body::from("not json"))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = r#"{"type":"fail"}"#;
+        let request = Request::post("/webhooks")
+            .header("X-Webhook-Signature", sign(body))
+            .body(Body::from(body))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn test_track_events_layer() {
+        let bento = FakeBento::new();
+        let buffer = Arc::new(EventBuffer::builder(bento.client()).build().unwrap());
+        let layer = TrackEventsLayer::new(Arc::clone(&buffer))
+            .track(Method::GET, "/posts/:id", page_view)
+            .track(Method::GET, "/docs/*", page_view)
+            .track(Method::GET, "/missing", page_view);
+
+        let app: Router = Router::new()
+            .route("/posts/:id", get(|| async { "post" }))
+            .route("/docs/*rest", get(|| async { "docs" }))
+            .route("/missing", get(|| async { StatusCode::NOT_FOUND }))
+            .layer(layer);
+
+        for (uri, email) in [
+            ("/posts/1", Some("reader@example.com")),
+            ("/docs/guide/intro", Some("reader@example.com")),
+            ("/posts/2", None),
+            ("/missing", Some("reader@example.com")),
+        ] {
+            let mut request = Request::get(uri);
+            if let Some(email) = email {
+                request = request.header("x-user-email", email);
+            }
+            let response = app.clone().oneshot(request.body(Body::empty()).unwrap()).await.unwrap();
+            assert!(response.status().is_success() || uri == "/missing");
+        }
+
+        assert!(bento.events().is_empty());
+        buffer.flush().await.unwrap();
+        assert_eq!(bento.requests().len(), 1);
+        let mut paths: Vec<_> = bento.events_for("reader@example.com").iter()
+            .map(|event| event.details.as_ref().unwrap()["path"].clone())
+            .collect();
+        paths.sort_by_key(|path| path.to_string());
+        assert_eq!(paths, vec![serde_json::json!("/docs/guide/intro"), serde_json::json!("/posts/1")]);
+    }
+
+    #[test]
+    fn test_rule_matching() {
+        let rule = TrackRule {
+            method: Method::GET,
+            pattern: "/docs/*".into(),
+            build: Arc::new(|_| None),
+        };
+        let parts = |method: Method, uri: &str| Request::builder().method(method).uri(uri).body(()).unwrap().into_parts().0;
+
+        assert!(rule.matches(&parts(Method::GET, "/docs")));
+        assert!(rule.matches(&parts(Method::GET, "/docs/a/b")));
+        assert!(!rule.matches(&parts(Method::GET, "/docsearch")));
+        assert!(!rule.matches(&parts(Method::POST, "/docs/a")));
+    }
+}
\ No newline at end of file
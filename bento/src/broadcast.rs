@@ -4,11 +4,11 @@ use tracing::instrument;
 
 impl Client {
     /// Get all broadcasts
-    #[instrument(skip(self))]
+    #[instrument(skip_all)]
     pub async fn get_broadcasts(&self) -> Result<Vec<BroadcastData>> {
         let url = self.build_url("/fetch/broadcasts")?;
         let response = self.request(
-            self.http_client.get(&url)
+            self.get(&url)
         ).await?;
 
         #[derive(Deserialize)]
@@ -16,12 +16,12 @@ impl Client {
             broadcasts: Vec<BroadcastData>,
         }
 
-        let broadcast_response: BroadcastResponse = response.json().await?;
+        let broadcast_response: BroadcastResponse = response.json()?;
         Ok(broadcast_response.broadcasts)
     }
 
     /// Create new broadcasts
-    #[instrument(skip(self))]
+    #[instrument(skip_all, fields(count = broadcasts.len()))]
     pub async fn create_broadcasts(&self, broadcasts: Vec<BroadcastData>) -> Result<()> {
         if broadcasts.is_empty() {
             return Err(Error::InvalidRequest("No broadcasts provided".into()));
@@ -37,9 +37,6 @@ impl Client {
             if broadcast.content.is_empty() {
                 return Err(Error::InvalidRequest("Content is required".into()));
             }
-            if !broadcast.from.email.contains('@') {
-                return Err(Error::InvalidEmail(broadcast.from.email.clone()));
-            }
             if broadcast.batch_size_per_hour == 0 {
                 return Err(Error::InvalidBatchSize("Batch size must be positive".into()));
             }
@@ -47,16 +44,15 @@ impl Client {
 
         let url = self.build_url("/batch/broadcasts")?;
         let response = self.request(
-            self.http_client
-                .post(&url)
+            self.post(&url)
                 .json(&serde_json::json!({
                     "broadcasts": broadcasts
                 }))
         ).await?;
 
-        if !response.status().is_success() {
+        if !response.is_success() {
             return Err(Error::UnexpectedResponse(
-                format!("Failed to create broadcasts: {}", response.status())
+                format!("Failed to create broadcasts: {}", response.status)
             ));
         }
 
@@ -121,7 +117,7 @@ mod tests {
             broadcast_type: BroadcastType::Plain,
             from: ContactData {
                 name: Some("Test Sender".into()),
-                email: "sender@example.com".into(),
+                email: "sender@example.com".parse().unwrap(),
             },
             inclusive_tags: None,
             exclusive_tags: None,
@@ -145,7 +141,7 @@ mod tests {
             broadcast_type: BroadcastType::Plain,
             from: ContactData {
                 name: None,
-                email: "invalid-email".into(),
+                email: "sender@example.com".parse().unwrap(),
             },
             inclusive_tags: None,
             exclusive_tags: None,
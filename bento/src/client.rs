@@ -1,98 +1,220 @@
 //! Client implementation for making HTTP requests.
 
-use crate::{Config, Error};
-use reqwest::{Client as ReqwestClient, RequestBuilder};
-use std::time::Duration;
+use crate::rate_limit::RateLimiter;
+use crate::transport::RequestBuilder;
+use crate::{retry, Config, Error, HttpRequest, HttpResponse, Method, ReqwestTransport, Transport};
+use std::fmt;
 use std::sync::Arc;
-use tracing::{error, instrument};
+use std::time::Instant;
+use tracing::field::Empty;
+use tracing::{error, instrument, warn, Span};
 use base64::engine::Engine;
+use secrecy::ExposeSecret;
+use secrecy::zeroize::Zeroizing;
 
 /// Client for making requests to the Bento API.
 ///
 /// This client handles authentication, retry logic, and request/response processing.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct Client {
     config: Arc<Config>,
-    pub(crate) http_client: ReqwestClient,
+    transport: Arc<dyn Transport>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+}
+
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Client {
     /// Creates a new client instance with the provided configuration.
     ///
+    /// Requests are sent through a [`ReqwestTransport`] using the configured timeout.
+    ///
     /// # Errors
     /// Returns an error if the HTTP client cannot be created.
     pub fn new(config: Config) -> crate::Result<Self> {
-        let http_client = ReqwestClient::builder()
-            .timeout(config.timeout)
-            .build()
-            .map_err(|e| Error::InvalidConfig(e.to_string()))?;
+        let transport = ReqwestTransport::new(config.timeout)?;
+        Ok(Self::with_transport(config, transport))
+    }
 
-        Ok(Self {
+    /// Creates a new client that sends requests through a custom transport.
+    ///
+    /// The configured timeout is not applied by the client; the transport is responsible
+    /// for enforcing its own timeouts.
+    pub fn with_transport(config: Config, transport: impl Transport + 'static) -> Self {
+        Self {
+            rate_limiter: config.rate_limit.map(|quota| Arc::new(RateLimiter::new(quota))),
             config: Arc::new(config),
-            http_client,
-        })
+            transport: Arc::new(transport),
+        }
+    }
+
+    /// Starts a GET request to the given URL.
+    pub(crate) fn get(&self, url: &str) -> RequestBuilder {
+        RequestBuilder::new(Method::Get, url)
+    }
+
+    /// Starts a POST request to the given URL.
+    pub(crate) fn post(&self, url: &str) -> RequestBuilder {
+        RequestBuilder::new(Method::Post, url)
+    }
+
+    /// Redacts an email address for use as a span field, following the configured
+    /// [`Redaction`](crate::Redaction) mode.
+    pub(crate) fn redact(&self, email: &str) -> String {
+        self.config.redaction.email(email)
     }
 
     /// Makes an HTTP request with automatic retry handling.
     ///
+    /// Configured [`Interceptor`](crate::Interceptor)s run before the first attempt and
+    /// after the final response or error. The span records the method, endpoint path, final status, total latency and
+    /// attempt count. The URL query and body are never recorded since they may
+    /// contain subscriber data.
+    ///
     /// # Errors
     /// Returns an error if the request fails after retries or receives an error response.
-    #[instrument(skip(self))]
-    pub(crate) async fn request(&self, builder: RequestBuilder) -> crate::Result<reqwest::Response> {
-        let response = self.execute_with_retry(builder).await?;
-
-        match response.status() {
-            status if status.is_success() => Ok(response),
-            status if status.as_u16() == 429 => Err(Error::RateLimit),
-            status if status.as_u16() == 401 => Err(Error::AuthenticationFailed),
+    #[instrument(skip_all, fields(method = Empty, endpoint = Empty, status = Empty, latency_ms = Empty, attempt = Empty))]
+    pub(crate) async fn request(&self, builder: RequestBuilder) -> crate::Result<HttpResponse> {
+        let mut request = builder.build()?;
+        self.config.interceptors.on_request(&mut request).await?;
+
+        let endpoint = self.endpoint(&request);
+        let span = Span::current();
+        span.record("method", request.method.as_str());
+        span.record("endpoint", endpoint.as_str());
+
+        let started = Instant::now();
+        let response = self.execute_with_retry(&request).await;
+        let elapsed = started.elapsed();
+        span.record("latency_ms", elapsed.as_millis() as u64);
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_request(request.method, &endpoint, response.as_ref().ok().map(|r| r.status), elapsed);
+        match &response {
+            Ok(response) => self.config.interceptors.on_response(&request, response, elapsed).await,
+            Err(err) => self.config.interceptors.on_error(&request, err, elapsed).await,
+        }
+        let response = response?;
+        span.record("status", response.status);
+
+        if response.is_success() {
+            return Ok(response);
+        }
+
+        match response.status {
+            429 => Err(Error::RateLimit),
+            401 => Err(Error::AuthenticationFailed),
             status => {
-                let error_msg = response.text().await
-                    .unwrap_or_else(|_| "Unknown error".to_string());
-                error!(?status, error = ?error_msg, "API request failed");
-                Err(Error::UnexpectedResponse(error_msg))
+                let message = response.text();
+                let request_id = response.header("X-Request-Id").map(str::to_string);
+                // The body can echo subscriber data back, so only its size is logged
+                error!(status, %endpoint, ?request_id, body_len = message.len(), "API request failed");
+                Err(Error::Api {
+                    status,
+                    endpoint,
+                    body: serde_json::from_str(&message).ok(),
+                    message,
+                    request_id,
+                })
             }
         }
     }
 
-    /// Executes a request with retry logic for rate limiting.
-    async fn execute_with_retry(&self, builder: RequestBuilder) -> crate::Result<reqwest::Response> {
-        let retry_strategy = tokio_retry::strategy::ExponentialBackoff::from_millis(100)
-            .max_delay(Duration::from_secs(5))
-            .take(3);
-
-        let config = Arc::clone(&self.config);
-        let original_builder = builder.try_clone()
-            .ok_or_else(|| Error::InvalidRequest("Failed to clone request".into()))?;
-
-        tokio_retry::RetryIf::spawn(
-            retry_strategy,
-            move || {
-                let builder = original_builder.try_clone()
-                    .ok_or_else(|| Error::InvalidRequest("Failed to clone request".into()));
-                let config = Arc::clone(&config);
-
-                async move {
-                    let builder = builder?;
-                    let response = builder
-                        .header("Authorization", format!("Basic {}", base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", config.publishable_key, config.secret_key))))
-                        .header("Accept", "application/json")
-                        .header("Content-Type", "application/json")
-                        .header(
-                            "User-Agent",
-                            format!(
-                                "bento-rust-{}-{}",
-                                crate::VERSION,
-                                config.site_uuid
-                            ),
-                        )
-                        .send()
-                        .await?;
-
-                    Ok(response)
+    /// Executes a request, retrying transient failures.
+    ///
+    /// When a rate limit is configured every attempt waits for a token first.
+    /// Retries follow the configured [`RetryPolicy`](crate::RetryPolicy). The delay honors
+    /// the `Retry-After` header when present and otherwise uses exponential backoff with
+    /// jitter. A `Retry-After` longer than the policy's `max_retry_after` is not waited
+    /// for. Once retries are exhausted the final response is returned so its status
+    /// can be reported.
+    async fn execute_with_retry(&self, request: &HttpRequest) -> crate::Result<HttpResponse> {
+        let policy = &self.config.retry_policy;
+        let idempotent = retry::is_idempotent(request.method, request.path());
+        #[cfg(feature = "metrics")]
+        let endpoint = self.endpoint(request);
+        let request = self.authorize(request.clone());
+        let mut attempt = 0;
+
+        loop {
+            Span::current().record("attempt", attempt + 1);
+            if let Some(limiter) = &self.rate_limiter {
+                if limiter.acquire().await {
+                    #[cfg(feature = "metrics")]
+                    crate::metrics::record_rate_limited(&endpoint, "client");
+                }
+            }
+
+            let delay = match self.transport.send(request.clone()).await {
+                Ok(response) => {
+                    #[cfg(feature = "metrics")]
+                    if response.status == 429 {
+                        crate::metrics::record_rate_limited(&endpoint, "server");
+                    }
+                    if attempt >= policy.max_retries || !policy.is_retryable_status(response.status, idempotent) {
+                        return Ok(response);
+                    }
+                    match retry::retry_after(response.header("Retry-After")) {
+                        Some(delay) if delay > policy.max_retry_after => return Ok(response),
+                        Some(delay) => delay,
+                        None => policy.backoff_delay(attempt),
+                    }
+                }
+                Err(err) => {
+                    if attempt >= policy.max_retries || !policy.is_retryable_error(&err, idempotent) {
+                        return Err(err);
+                    }
+                    policy.backoff_delay(attempt)
                 }
-            },
-            |err: &Error| matches!(err, Error::RateLimit),
-        ).await
+            };
+
+            attempt += 1;
+            #[cfg(feature = "metrics")]
+            crate::metrics::record_retry(&endpoint);
+            warn!(attempt, ?delay, "retrying request after transient failure");
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Adds authentication and standard headers to a request.
+    ///
+    /// The `Authorization` header is redacted from the request's `Debug` output and
+    /// marked sensitive by the default transport. This method is deliberately not
+    /// instrumented so the header never becomes a span field.
+    fn authorize(&self, mut request: HttpRequest) -> HttpRequest {
+        let credentials = Zeroizing::new(format!(
+            "{}:{}",
+            self.config.publishable_key,
+            self.config.secret_key.expose_secret()
+        ));
+
+        request.headers.extend([
+            ("Authorization".to_string(), format!("Basic {}", base64::engine::general_purpose::STANDARD.encode(credentials.as_bytes()))),
+            ("Accept".to_string(), "application/json".to_string()),
+            ("Content-Type".to_string(), "application/json".to_string()),
+            (
+                "User-Agent".to_string(),
+                format!(
+                    "bento-rust-{}-{}",
+                    crate::VERSION,
+                    self.config.site_uuid
+                ),
+            ),
+        ]);
+        request
+    }
+
+    /// Returns the request path relative to the configured base URL, without the query.
+    fn endpoint(&self, request: &HttpRequest) -> String {
+        let base = self.config.base_url.trim_end_matches('/');
+        let path = request.url.strip_prefix(base).unwrap_or(request.path());
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        format!("/{}", path.trim_start_matches('/'))
     }
 
     /// Builds a URL by combining the base URL with the provided path.
@@ -121,15 +243,36 @@ mod tests {
     use std::time::Duration;
     use wiremock::{Mock, MockServer, ResponseTemplate};
     use wiremock::matchers::{method, path};
+    use crate::RetryPolicy;
+    use std::sync::Mutex;
+
+    /// In-memory transport that replays canned responses and records requests
+    #[derive(Default)]
+    struct RecordingTransport {
+        responses: Mutex<Vec<crate::Result<HttpResponse>>>,
+        requests: Mutex<Vec<HttpRequest>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Transport for Arc<RecordingTransport> {
+        async fn send(&self, request: HttpRequest) -> crate::Result<HttpResponse> {
+            self.requests.lock().unwrap().push(request);
+            self.responses.lock().unwrap().remove(0)
+        }
+    }
 
     #[tokio::test]
     async fn test_client_creation() {
         let config = Config {
             publishable_key: "pub_key".into(),
-            secret_key: "secret_key".into(),
+            secret_key: secrecy::SecretString::new("secret_key".into()),
             site_uuid: "site_123".into(),
             timeout: Duration::from_secs(30),
             base_url: "https://api.test.com".into(),
+            retry_policy: RetryPolicy::default(),
+            rate_limit: None,
+            redaction: crate::Redaction::default(),
+            interceptors: Default::default(),
         };
 
         let client = Client::new(config);
@@ -149,16 +292,602 @@ mod tests {
 
         let config = Config {
             publishable_key: "invalid".into(),
-            secret_key: "invalid".into(),
+            secret_key: secrecy::SecretString::new("invalid".into()),
             site_uuid: "site_123".into(),
             timeout: Duration::from_secs(30),
             base_url: mock_server.uri(),
+            retry_policy: RetryPolicy::default(),
+            rate_limit: None,
+            redaction: crate::Redaction::default(),
+            interceptors: Default::default(),
         };
 
         let client = Client::new(config).unwrap();
-        let request = client.http_client.get(format!("{}/test", mock_server.uri()));
+        let request = client.get(&format!("{}/test", mock_server.uri()));
         let response = client.request(request).await;
 
         assert!(matches!(response, Err(Error::AuthenticationFailed)));
     }
+
+    #[tokio::test]
+    async fn test_retries_transient_status() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/test"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/test"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = crate::test_utils::create_test_client(mock_server.uri());
+        let request = client.get(&format!("{}/test", mock_server.uri()));
+        let response = client.request(request).await;
+
+        assert!(response.is_ok(), "Expected OK, got {:?}", response);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_returned_after_retries_exhausted() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/test"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+            .expect(4)
+            .mount(&mock_server)
+            .await;
+
+        let client = crate::test_utils::create_test_client(mock_server.uri());
+        let request = client.get(&format!("{}/test", mock_server.uri()));
+        let response = client.request(request).await;
+
+        assert!(matches!(response, Err(Error::RateLimit)));
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_header_is_honored() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/test"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/test"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+
+        let client = crate::test_utils::create_test_client(mock_server.uri());
+        let request = client.get(&format!("{}/test", mock_server.uri()));
+
+        let started = std::time::Instant::now();
+        let response = client.request(request).await;
+
+        assert!(response.is_ok());
+        assert!(started.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_not_capped_by_max_delay() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/test"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/test"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+
+        let config = crate::ConfigBuilder::new()
+            .publishable_key("pub_key")
+            .secret_key("secret_key")
+            .site_uuid("site_123")
+            .base_url(mock_server.uri())
+            .retry_policy(RetryPolicy::new().max_delay(Duration::from_millis(10)))
+            .build()
+            .unwrap();
+        let client = Client::new(config).unwrap();
+        let request = client.get(&format!("{}/test", mock_server.uri()));
+
+        let started = std::time::Instant::now();
+        let response = client.request(request).await;
+
+        assert!(response.is_ok());
+        assert!(started.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_above_limit_is_not_retried() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/test"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "120"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = crate::test_utils::create_test_client(mock_server.uri());
+        let request = client.get(&format!("{}/test", mock_server.uri()));
+        let response = client.request(request).await;
+
+        assert!(matches!(response, Err(Error::RateLimit)));
+    }
+
+    #[tokio::test]
+    async fn test_non_retryable_status_is_not_retried() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/test"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = crate::test_utils::create_test_client(mock_server.uri());
+        let request = client.get(&format!("{}/test", mock_server.uri()));
+        let response = client.request(request).await;
+
+        assert_eq!(response.unwrap_err().status(), Some(500));
+    }
+
+    #[tokio::test]
+    async fn test_retries_disabled() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/test"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let config = crate::ConfigBuilder::new()
+            .publishable_key("pub_key")
+            .secret_key("secret_key")
+            .site_uuid("site_123")
+            .base_url(mock_server.uri())
+            .max_retries(0)
+            .build()
+            .unwrap();
+
+        let client = Client::new(config).unwrap();
+        let request = client.get(&format!("{}/test", mock_server.uri()));
+        let response = client.request(request).await;
+
+        assert!(matches!(response, Err(Error::Api { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_non_idempotent_post_not_retried_by_default() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/batch/emails"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = crate::test_utils::create_test_client(mock_server.uri());
+        let request = client.post(&format!("{}/batch/emails", mock_server.uri()));
+        let response = client.request(request).await;
+
+        assert!(matches!(response, Err(Error::Api { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_non_idempotent_post_retried_when_allowed() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/batch/events"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/batch/events"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "results": 1 })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let config = crate::ConfigBuilder::new()
+            .publishable_key("pub_key")
+            .secret_key("secret_key")
+            .site_uuid("site_123")
+            .base_url(mock_server.uri())
+            .retry_policy(RetryPolicy::new().retry_non_idempotent(true).base_delay(Duration::from_millis(1)))
+            .build()
+            .unwrap();
+
+        let client = Client::new(config).unwrap();
+        let request = client.post(&format!("{}/batch/events", mock_server.uri()));
+        let response = client.request(request).await.unwrap();
+
+        assert_eq!(response.status, 200);
+    }
+
+    #[tokio::test]
+    async fn test_custom_transport() {
+        let transport = Arc::new(RecordingTransport::default());
+        transport.responses.lock().unwrap().extend([
+            Err(Error::Connection("connection refused".into())),
+            Ok(HttpResponse::new(200, "{}")),
+        ]);
+
+        let config = crate::ConfigBuilder::new()
+            .publishable_key("pub_key")
+            .secret_key("secret_key")
+            .site_uuid("site_123")
+            .base_url("https://api.test.com")
+            .build()
+            .unwrap();
+
+        let client = Client::with_transport(config, Arc::clone(&transport));
+        let url = client.build_url("/test").unwrap();
+        let response = client.request(client.get(&url)).await;
+        assert!(response.is_ok(), "Expected OK, got {:?}", response);
+
+        let requests = transport.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, Method::Get);
+        assert_eq!(requests[0].url, "https://api.test.com/test?site_uuid=site_123");
+        assert_eq!(requests[0].header("authorization"), Some("Basic cHViX2tleTpzZWNyZXRfa2V5"));
+        assert_eq!(requests[0].header("user-agent"), Some(format!("bento-rust-{}-site_123", crate::VERSION).as_str()));
+    }
+
+    #[tokio::test]
+    async fn test_api_error_details() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/fetch/subscribers"))
+            .respond_with(ResponseTemplate::new(422)
+                .insert_header("X-Request-Id", "req_123")
+                .set_body_json(serde_json::json!({
+                    "error": "jesse@example.com is invalid"
+                })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let (logs, _guard) = capture_traces();
+        let client = crate::test_utils::create_test_client(mock_server.uri());
+        let url = client.build_url("/fetch/subscribers").unwrap();
+        let response = client.request(client.get(&url).query(&[("email", "jesse@example.com")])).await;
+
+        let err = response.unwrap_err();
+        assert_eq!(err.to_string(), "API error 422 on /fetch/subscribers (request req_123)");
+        match err {
+            Error::Api { status, endpoint, body, request_id, .. } => {
+                assert_eq!(status, 422);
+                assert_eq!(endpoint, "/fetch/subscribers");
+                assert_eq!(body, Some(serde_json::json!({ "error": "jesse@example.com is invalid" })));
+                assert_eq!(request_id.as_deref(), Some("req_123"));
+            }
+            other => panic!("Expected API error, got {:?}", other),
+        }
+
+        let output = logs.output();
+        assert!(output.contains("API request failed"), "{}", output);
+        assert!(output.contains("req_123"), "{}", output);
+        assert!(!output.contains("jesse@example.com"), "{}", output);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_limit_shared_across_clones() {
+        let transport = Arc::new(RecordingTransport::default());
+        transport.responses.lock().unwrap().extend((0..4).map(|_| Ok(HttpResponse::new(200, "{}"))));
+
+        let config = crate::ConfigBuilder::new()
+            .publishable_key("pub_key")
+            .secret_key("secret_key")
+            .site_uuid("site_123")
+            .base_url("https://api.test.com")
+            .rate_limit(2, Duration::from_secs(1))
+            .build()
+            .unwrap();
+
+        let client = Client::with_transport(config, Arc::clone(&transport));
+        let clone = client.clone();
+        let url = client.build_url("/test").unwrap();
+        let started = tokio::time::Instant::now();
+
+        client.request(client.get(&url)).await.unwrap();
+        clone.request(clone.get(&url)).await.unwrap();
+        assert_eq!(started.elapsed(), Duration::ZERO);
+
+        client.request(client.get(&url)).await.unwrap();
+        clone.request(clone.get(&url)).await.unwrap();
+        assert_eq!(started.elapsed(), Duration::from_secs(1));
+    }
+
+    /// Writer collecting formatted trace output
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Records all spans and events at every level into a buffer until the guard is dropped
+    fn capture_traces() -> (CapturedLogs, tracing::subscriber::DefaultGuard) {
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::TRACE)
+            .with_span_events(tracing_subscriber::fmt::format::FmtSpan::FULL)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+
+        (logs, tracing::subscriber::set_default(subscriber))
+    }
+
+    impl CapturedLogs {
+        fn output(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_secrets_never_traced() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/fetch/tags"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "data": [] })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/batch/events"))
+            .respond_with(ResponseTemplate::new(503).set_body_string("boom"))
+            .mount(&mock_server)
+            .await;
+
+        let (logs, _guard) = capture_traces();
+
+        let config = crate::ConfigBuilder::new()
+            .publishable_key("pub_key")
+            .secret_key("super_secret_value")
+            .site_uuid("site_123")
+            .base_url(mock_server.uri())
+            .retry_policy(RetryPolicy::new().max_retries(1).base_delay(Duration::from_millis(1)).retry_non_idempotent(true))
+            .build()
+            .unwrap();
+        let client = Client::new(config).unwrap();
+        tracing::info!(?client, "created client");
+
+        client.get_tags().await.unwrap();
+        let event = crate::EventData {
+            event_type: "$login".into(),
+            email: "test@example.com".parse().unwrap(),
+            fields: None,
+            details: None,
+        };
+        assert!(client.track_events(vec![event]).await.is_err());
+
+        let authorization = client.authorize(HttpRequest::new(crate::Method::Get, mock_server.uri()));
+        tracing::info!(request = ?authorization, "authorized request");
+
+        let encoded = base64::engine::general_purpose::STANDARD.encode("pub_key:super_secret_value");
+        let output = logs.output();
+        assert!(output.contains("retrying request"), "{}", output);
+        assert!(output.contains("authorized request"), "{}", output);
+        assert!(!output.contains("super_secret_value"), "{}", output);
+        assert!(!output.contains(&encoded), "{}", output);
+    }
+
+    #[tokio::test]
+    async fn test_spans_redact_personal_data() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/fetch/subscribers"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "id": "1",
+                    "type": "visitors",
+                    "attributes": { "uuid": "abc", "email": "jesse@example.com", "fields": {}, "cached_tag_ids": [], "unsubscribed_at": null }
+                }
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/batch/events"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "results": 1, "failed": 0 })))
+            .mount(&mock_server)
+            .await;
+
+        for (redaction, expected) in [
+            (crate::Redaction::Mask, "j***@example.com".to_string()),
+            (crate::Redaction::hash("test-key"), crate::Redaction::hash("test-key").email("jesse@example.com")),
+        ] {
+            let (logs, _guard) = capture_traces();
+            let config = crate::ConfigBuilder::new()
+                .publishable_key("pub_key")
+                .secret_key("secret_key")
+                .site_uuid("site_123")
+                .base_url(mock_server.uri())
+                .redaction(redaction)
+                .build()
+                .unwrap();
+            let client = Client::new(config).unwrap();
+
+            client.find_subscriber("jesse@example.com").await.unwrap();
+            let event = crate::EventData {
+                event_type: "$purchase".into(),
+                email: "jesse@example.com".parse().unwrap(),
+                fields: None,
+                details: Some([("card_last4".to_string(), serde_json::json!("4242"))].into()),
+            };
+            client.track_events(vec![event]).await.unwrap();
+
+            let output = logs.output();
+            assert!(output.contains(&format!("email={}", expected)), "{}", output);
+            assert!(!output.contains("jesse@example.com"), "{}", output);
+            assert!(!output.contains("4242"), "{}", output);
+            assert!(output.contains(r#"endpoint="/fetch/subscribers""#), "{}", output);
+            assert!(output.contains(r#"endpoint="/batch/events""#), "{}", output);
+            assert!(output.contains("count=1"), "{}", output);
+            assert!(output.contains("status=200"), "{}", output);
+            assert!(output.contains("attempt=1"), "{}", output);
+            assert!(output.contains("latency_ms="), "{}", output);
+        }
+    }
+
+    /// Interceptor recording each hook call and tagging requests with a correlation id
+    #[derive(Default)]
+    struct AuditInterceptor {
+        calls: Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl crate::Interceptor for Arc<AuditInterceptor> {
+        async fn on_request(&self, request: &mut HttpRequest) -> crate::Result<()> {
+            assert!(request.header("Authorization").is_none());
+            request.headers.push(("X-Correlation-Id".into(), "corr-1".into()));
+            self.calls.lock().unwrap().push(format!("request {}", request.path()));
+            Ok(())
+        }
+
+        async fn on_response(&self, request: &HttpRequest, response: &HttpResponse, _elapsed: Duration) {
+            self.calls.lock().unwrap().push(format!("response {} {}", request.path(), response.status));
+        }
+
+        async fn on_error(&self, request: &HttpRequest, error: &Error, _elapsed: Duration) {
+            self.calls.lock().unwrap().push(format!("error {} {}", request.path(), error));
+        }
+    }
+
+    struct RejectAll;
+
+    #[async_trait::async_trait]
+    impl crate::Interceptor for RejectAll {
+        async fn on_request(&self, _request: &mut HttpRequest) -> crate::Result<()> {
+            Err(Error::InvalidRequest("rejected by interceptor".into()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_interceptors_run_once_per_call() {
+        let transport = Arc::new(RecordingTransport::default());
+        transport.responses.lock().unwrap().extend([
+            Ok(HttpResponse::new(503, "")),
+            Ok(HttpResponse::new(200, "{}")),
+            Err(Error::Connection("connection refused".into())),
+            Err(Error::Connection("connection refused".into())),
+        ]);
+        let audit = Arc::new(AuditInterceptor::default());
+
+        let config = crate::ConfigBuilder::new()
+            .publishable_key("pub_key")
+            .secret_key("secret_key")
+            .site_uuid("site_123")
+            .base_url("https://api.test.com")
+            .retry_policy(RetryPolicy::new().max_retries(1).base_delay(Duration::from_millis(1)))
+            .interceptor(Arc::clone(&audit))
+            .build()
+            .unwrap();
+
+        let client = Client::with_transport(config, Arc::clone(&transport));
+        let url = client.build_url("/test").unwrap();
+        client.request(client.get(&url)).await.unwrap();
+        assert!(matches!(client.request(client.get(&url)).await, Err(Error::Connection(_))));
+
+        assert_eq!(*audit.calls.lock().unwrap(), [
+            "request /test",
+            "response /test 200",
+            "request /test",
+            "error /test connection failed: connection refused",
+        ]);
+
+        let requests = transport.requests.lock().unwrap();
+        assert_eq!(requests.len(), 4);
+        for request in requests.iter() {
+            assert_eq!(request.header("X-Correlation-Id"), Some("corr-1"));
+            assert!(request.header("Authorization").is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_interceptor_can_abort_request() {
+        let transport = Arc::new(RecordingTransport::default());
+        let audit = Arc::new(AuditInterceptor::default());
+
+        let config = crate::ConfigBuilder::new()
+            .publishable_key("pub_key")
+            .secret_key("secret_key")
+            .site_uuid("site_123")
+            .base_url("https://api.test.com")
+            .interceptor(RejectAll)
+            .interceptor(Arc::clone(&audit))
+            .build()
+            .unwrap();
+
+        let client = Client::with_transport(config, Arc::clone(&transport));
+        let url = client.build_url("/test").unwrap();
+        let result = client.request(client.get(&url)).await;
+
+        assert!(matches!(result, Err(Error::InvalidRequest(message)) if message == "rejected by interceptor"));
+        assert!(audit.calls.lock().unwrap().is_empty());
+        assert!(transport.requests.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_aborted_request_reaches_earlier_interceptors() {
+        let transport = Arc::new(RecordingTransport::default());
+        let audit = Arc::new(AuditInterceptor::default());
+
+        let config = crate::ConfigBuilder::new()
+            .publishable_key("pub_key")
+            .secret_key("secret_key")
+            .site_uuid("site_123")
+            .base_url("https://api.test.com")
+            .interceptor(Arc::clone(&audit))
+            .interceptor(RejectAll)
+            .build()
+            .unwrap();
+
+        let client = Client::with_transport(config, Arc::clone(&transport));
+        let url = client.build_url("/test").unwrap();
+        let result = client.request(client.get(&url)).await;
+
+        assert!(matches!(result, Err(Error::InvalidRequest(_))));
+        assert_eq!(*audit.calls.lock().unwrap(), [
+            "request /test",
+            "error /test invalid request parameters: rejected by interceptor",
+        ]);
+        assert!(transport.requests.lock().unwrap().is_empty());
+    }
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use crate::{Client, CommandData, CommandResponse, Error, Result};
+use crate::{BatchOutcome, Client, Command, Error, Result};
 use tracing::instrument;
 
 impl Client {
@@ -8,55 +8,50 @@ impl Client {
     /// * `commands` - Vector of commands to execute
     ///
     /// # Returns
-    /// * `Result<()>` - Success or error
+    /// * `Result<BatchOutcome>` - Number of applied and failed commands
     ///
     /// # Errors
     /// * `Error::InvalidRequest` if no commands are provided
-    /// * `Error::InvalidEmail` if any email is invalid
-    /// * `Error::InvalidRequest` if any command query is empty
-    /// * `Error::InvalidCommand` if an invalid command type is provided
-    /// * `Error::UnexpectedResponse` if the API returns an error
-    #[instrument(skip(self))]
-    pub async fn subscriber_command(&self, commands: Vec<CommandData>) -> Result<()> {
+    /// * `Error::InvalidRequest` if any tag or field key is empty
+    /// * `Error::Api` if the API returns an error status
+    #[instrument(skip_all, fields(count = commands.len()))]
+    pub async fn subscriber_command(&self, commands: Vec<Command>) -> Result<BatchOutcome> {
         if commands.is_empty() {
             return Err(Error::InvalidRequest("No commands provided".into()));
         }
 
         for command in &commands {
-            if !command.email.contains('@') {
-                return Err(Error::InvalidEmail(command.email.clone()));
-            }
-            if command.query.is_empty() {
-                return Err(Error::InvalidRequest("Command query is required".into()));
+            match command {
+                Command::AddTag { tag, .. }
+                | Command::AddTagViaEvent { tag, .. }
+                | Command::RemoveTag { tag, .. } if tag.is_empty() => {
+                    return Err(Error::InvalidRequest("Command tag is required".into()));
+                }
+                Command::AddField { key, .. } | Command::RemoveField { key, .. } if key.is_empty() => {
+                    return Err(Error::InvalidRequest("Command field key is required".into()));
+                }
+                _ => {}
             }
         }
 
         let url = self.build_url("/fetch/commands")?;
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_batch("/fetch/commands", commands.len());
         let response = self.request(
-            self.http_client
-                .post(&url)
+            self.post(&url)
                 .json(&serde_json::json!({
                     "command": commands
                 }))
         ).await?;
 
-        let command_response: CommandResponse = response.json().await?;
-
-        if command_response.failed > 0 {
-            return Err(Error::UnexpectedResponse(
-                format!("Command execution partially failed: {} succeeded, {} failed",
-                        command_response.results, command_response.failed)
-            ));
-        }
-
-        Ok(())
+        let outcome: BatchOutcome = response.json()?;
+        Ok(outcome)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::CommandType;
     use wiremock::{Mock, MockServer, ResponseTemplate};
     use wiremock::matchers::{method, path, body_json};
     use serde_json::json;
@@ -84,11 +79,7 @@ mod tests {
 
         let client = crate::test_utils::create_test_client(mock_server.uri());
 
-        let command = CommandData {
-            command: CommandType::AddTag,
-            email: "test@example.com".to_string(),
-            query: "new-tag".to_string(),
-        };
+        let command = Command::add_tag("test@example.com".parse().unwrap(), "new-tag");
 
         let result = client.subscriber_command(vec![command]).await;
         assert!(result.is_ok());
@@ -103,21 +94,20 @@ mod tests {
             .respond_with(ResponseTemplate::new(200)
                 .set_body_json(json!({
                     "results": 0,
-                    "failed": 1
+                    "failed": 1,
+                    "errors": ["subscriber not found"]
                 })))
             .mount(&mock_server)
             .await;
 
         let client = crate::test_utils::create_test_client(mock_server.uri());
 
-        let command = CommandData {
-            command: CommandType::Subscribe,
-            email: "test@example.com".to_string(),
-            query: "subscribe".to_string(),
-        };
+        let command = Command::subscribe("test@example.com".parse().unwrap());
 
-        let result = client.subscriber_command(vec![command]).await;
-        assert!(matches!(result, Err(Error::UnexpectedResponse(_))));
+        let outcome = client.subscriber_command(vec![command]).await.unwrap();
+        assert_eq!(outcome.succeeded, 0);
+        assert_eq!(outcome.failed, 1);
+        assert_eq!(outcome.errors[0].message.as_deref(), Some("subscriber not found"));
     }
 
     #[tokio::test]
@@ -129,22 +119,66 @@ mod tests {
         let result = client.subscriber_command(vec![]).await;
         assert!(matches!(result, Err(Error::InvalidRequest(_))));
 
-        // Test invalid email
-        let command = CommandData {
-            command: CommandType::AddTag,
-            email: "invalid-email".to_string(),
-            query: "test-tag".to_string(),
-        };
-        let result = client.subscriber_command(vec![command]).await;
-        assert!(matches!(result, Err(Error::InvalidEmail(_))));
-
-        // Test empty query
-        let command = CommandData {
-            command: CommandType::AddTag,
-            email: "test@example.com".to_string(),
-            query: "".to_string(),
-        };
-        let result = client.subscriber_command(vec![command]).await;
+        // Test invalid email is rejected when the command is built
+        let result = serde_json::from_value::<Command>(json!({
+            "command": "add_tag",
+            "email": "invalid-email",
+            "query": "test-tag"
+        }));
+        assert!(result.is_err());
+
+        // Test empty tag and field key
+        let email: crate::EmailAddress = "test@example.com".parse().unwrap();
+        let result = client.subscriber_command(vec![Command::add_tag(email.clone(), "")]).await;
         assert!(matches!(result, Err(Error::InvalidRequest(_))));
+
+        let result = client.subscriber_command(vec![Command::remove_field(email, "")]).await;
+        assert!(matches!(result, Err(Error::InvalidRequest(_))));
+    }
+
+    #[test]
+    fn test_command_wire_format() {
+        let email: crate::EmailAddress = "test@example.com".parse().unwrap();
+        let commands = vec![
+            Command::add_tag(email.clone(), "customer"),
+            Command::add_tag_via_event(email.clone(), "customer"),
+            Command::remove_tag(email.clone(), "lead"),
+            Command::add_field(email.clone(), "plan", "pro"),
+            Command::remove_field(email.clone(), "plan"),
+            Command::subscribe(email.clone()),
+            Command::unsubscribe(email.clone()),
+            Command::change_email(email, "new@example.com".parse().unwrap()),
+        ];
+
+        let value = serde_json::to_value(&commands).unwrap();
+        assert_eq!(value, json!([
+            { "command": "add_tag", "email": "test@example.com", "query": "customer" },
+            { "command": "add_tag_via_event", "email": "test@example.com", "query": "customer" },
+            { "command": "remove_tag", "email": "test@example.com", "query": "lead" },
+            { "command": "add_field", "email": "test@example.com", "query": r#"{"key":"plan","value":"pro"}"# },
+            { "command": "remove_field", "email": "test@example.com", "query": "plan" },
+            { "command": "subscribe", "email": "test@example.com", "query": "subscribe" },
+            { "command": "unsubscribe", "email": "test@example.com", "query": "unsubscribe" },
+            { "command": "change_email", "email": "test@example.com", "query": "new@example.com" }
+        ]));
+
+        let parsed: Vec<Command> = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed, commands);
+
+        // The query of subscribe commands is optional when reading
+        let parsed: Command = serde_json::from_value(json!({ "command": "subscribe", "email": "test@example.com" })).unwrap();
+        assert_eq!(parsed, commands[5]);
+    }
+
+    #[test]
+    fn test_command_deserialize_errors() {
+        for value in [
+            json!({ "command": "add_tag", "email": "test@example.com" }),
+            json!({ "command": "add_field", "email": "test@example.com", "query": "plan=pro" }),
+            json!({ "command": "change_email", "email": "test@example.com", "query": "not-an-email" }),
+            json!({ "command": "unknown", "email": "test@example.com", "query": "x" }),
+        ] {
+            assert!(serde_json::from_value::<Command>(value.clone()).is_err(), "{} should fail", value);
+        }
     }
 }
\ No newline at end of file
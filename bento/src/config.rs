@@ -1,24 +1,62 @@
 use crate::error::{Error, Result};
+use crate::interceptor::InterceptorChain;
+use crate::rate_limit::Quota;
+use crate::{Interceptor, Redaction, RetryPolicy};
+use secrecy::{ExposeSecret, SecretString};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
 
+/// Settings of one profile in a configuration file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Profile {
+    publishable_key: Option<String>,
+    secret_key: Option<SecretString>,
+    site_uuid: Option<String>,
+    timeout: Option<u64>,
+    base_url: Option<String>,
+    max_retries: Option<u32>,
+    rate_limit: Option<u32>,
+    redaction: Option<String>,
+    redaction_key: Option<SecretString>,
+}
+
 /// Configuration for the Bento client
+///
+/// The secret key is held in a [`SecretString`]: it is redacted from `Debug` output
+/// and zeroized when the configuration is dropped.
 #[derive(Debug, Clone)]
 pub struct Config {
     pub(crate) publishable_key: String,
-    pub(crate) secret_key: String,
+    pub(crate) secret_key: SecretString,
     pub(crate) site_uuid: String,
     pub(crate) timeout: Duration,
     pub(crate) base_url: String,
+    pub(crate) retry_policy: RetryPolicy,
+    pub(crate) rate_limit: Option<Quota>,
+    pub(crate) redaction: Redaction,
+    pub(crate) interceptors: InterceptorChain,
 }
 
 /// Builder for creating a Config
 #[derive(Debug, Default)]
 pub struct ConfigBuilder {
     publishable_key: Option<String>,
-    secret_key: Option<String>,
+    secret_key: Option<SecretString>,
     site_uuid: Option<String>,
     timeout: Option<Duration>,
     base_url: Option<String>,
+    max_retries: Option<u32>,
+    retry_policy: Option<RetryPolicy>,
+    rate_limit: Option<Quota>,
+    redaction: Option<Redaction>,
+    interceptors: InterceptorChain,
 }
 
 impl ConfigBuilder {
@@ -27,6 +65,115 @@ impl ConfigBuilder {
         Self::default()
     }
 
+    /// Create a builder from `BENTO_*` environment variables
+    ///
+    /// | Variable | Setting |
+    /// |---|---|
+    /// | `BENTO_PUBLISHABLE_KEY` | publishable key (required) |
+    /// | `BENTO_SECRET_KEY` | secret key (required) |
+    /// | `BENTO_SITE_UUID` | site UUID (required) |
+    /// | `BENTO_TIMEOUT` | request timeout in seconds |
+    /// | `BENTO_BASE_URL` | API base URL |
+    /// | `BENTO_MAX_RETRIES` | maximum retries for transient failures |
+    /// | `BENTO_RATE_LIMIT` | client-side limit in requests per second |
+    /// | `BENTO_REDACTION` | how emails appear in traces: `hash`, `mask` or `keep` |
+    /// | `BENTO_REDACTION_KEY` | HMAC key for `hash` redaction (required with `hash`) |
+    ///
+    /// Empty variables are treated as unset. The returned builder can still be
+    /// adjusted before calling [`ConfigBuilder::build`].
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidConfig` naming the variable if a required variable is
+    /// missing or a numeric variable cannot be parsed.
+    pub fn from_env() -> Result<Self> {
+        Self::from_vars(|name| env::var(name).ok())
+    }
+
+    /// Create a builder from a profile in a TOML configuration file
+    ///
+    /// Each profile is a table with the same settings as [`ConfigBuilder::from_env`],
+    /// in snake case:
+    ///
+    /// ```toml
+    /// [prod]
+    /// publishable_key = "..."
+    /// secret_key = "..."
+    /// site_uuid = "..."
+    ///
+    /// [staging]
+    /// publishable_key = "..."
+    /// secret_key = "..."
+    /// site_uuid = "..."
+    /// base_url = "https://staging.example.com/api/v1"
+    /// timeout = 10
+    /// max_retries = 1
+    /// rate_limit = 5
+    /// redaction = "hash"
+    /// redaction_key = "..."
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidConfig` if the file cannot be read or parsed, the profile
+    /// does not exist, or a required setting is missing from it.
+    pub fn from_file(path: impl AsRef<Path>, profile: &str) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .map_err(|e| Error::InvalidConfig(format!("cannot read {}: {}", path.display(), e)))?;
+        let mut profiles: HashMap<String, toml::Value> = toml::from_str(&contents)
+            .map_err(|e| Error::InvalidConfig(format!("invalid configuration file {}: {}", path.display(), e)))?;
+        let settings: Profile = profiles.remove(profile)
+            .ok_or_else(|| Error::InvalidConfig(format!("profile '{}' not found in {}", profile, path.display())))?
+            .try_into()
+            .map_err(|e| Error::InvalidConfig(format!("invalid profile '{}' in {}: {}", profile, path.display(), e)))?;
+
+        let missing = |setting: &str| {
+            Error::InvalidConfig(format!("profile '{}' in {} is missing {}", profile, path.display(), setting))
+        };
+
+        let mut builder = Self::new()
+            .publishable_key(settings.publishable_key.ok_or_else(|| missing("publishable_key"))?)
+            .site_uuid(settings.site_uuid.ok_or_else(|| missing("site_uuid"))?);
+
+        builder.secret_key = Some(settings.secret_key.ok_or_else(|| missing("secret_key"))?);
+
+        builder.timeout = settings.timeout.map(Duration::from_secs);
+        builder.base_url = settings.base_url;
+        builder.max_retries = settings.max_retries;
+        builder.rate_limit = settings.rate_limit.map(per_second);
+        builder.redaction = settings.redaction
+            .map(|mode| Redaction::parse(&mode, settings.redaction_key))
+            .transpose()
+            .map_err(|e| Error::InvalidConfig(format!("invalid profile '{}' in {}: {}", profile, path.display(), e)))?;
+
+        Ok(builder)
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let var = |name: &str| var(name).filter(|value| !value.is_empty());
+        let required = |name: &str| {
+            var(name).ok_or_else(|| Error::InvalidConfig(format!("environment variable {} is not set", name)))
+        };
+
+        let mut builder = Self::new()
+            .publishable_key(required("BENTO_PUBLISHABLE_KEY")?)
+            .secret_key(required("BENTO_SECRET_KEY")?)
+            .site_uuid(required("BENTO_SITE_UUID")?);
+
+        builder.timeout = parse_var("BENTO_TIMEOUT", var("BENTO_TIMEOUT"))?.map(Duration::from_secs);
+        builder.base_url = var("BENTO_BASE_URL");
+        builder.max_retries = parse_var("BENTO_MAX_RETRIES", var("BENTO_MAX_RETRIES"))?;
+        builder.rate_limit = parse_var("BENTO_RATE_LIMIT", var("BENTO_RATE_LIMIT"))?.map(per_second);
+        builder.redaction = var("BENTO_REDACTION")
+            .map(|value| {
+                Redaction::parse(&value, var("BENTO_REDACTION_KEY").map(SecretString::new)).map_err(|e| {
+                    Error::InvalidConfig(format!("environment variable BENTO_REDACTION: {}", e))
+                })
+            })
+            .transpose()?;
+
+        Ok(builder)
+    }
+
     /// Set the publishable key
     pub fn publishable_key(mut self, key: impl Into<String>) -> Self {
         self.publishable_key = Some(key.into());
@@ -34,8 +181,10 @@ impl ConfigBuilder {
     }
 
     /// Set the secret key
+    ///
+    /// The key is moved into a [`SecretString`] and never appears in `Debug` output.
     pub fn secret_key(mut self, key: impl Into<String>) -> Self {
-        self.secret_key = Some(key.into());
+        self.secret_key = Some(SecretString::new(key.into()));
         self
     }
 
@@ -57,6 +206,48 @@ impl ConfigBuilder {
         self
     }
 
+    /// Set the maximum number of retries for transient failures
+    ///
+    /// Shorthand for changing only `max_retries` on the retry policy. Takes precedence
+    /// over the value in a policy set with [`ConfigBuilder::retry_policy`].
+    /// Set to 0 to disable retries.
+    pub fn max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = Some(retries);
+        self
+    }
+
+    /// Set the retry policy
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Limit how fast requests are sent
+    ///
+    /// Allows bursts of up to `requests` and refills at `requests` per `per`. The limiter
+    /// is shared by every clone of a client, so concurrent tasks using one client wait
+    /// for capacity instead of receiving 429 responses from the API.
+    pub fn rate_limit(mut self, requests: u32, per: Duration) -> Self {
+        self.rate_limit = Some(Quota { requests, per });
+        self
+    }
+
+    /// Set how subscriber emails are recorded in tracing spans
+    ///
+    /// Defaults to [`Redaction::Mask`].
+    pub fn redaction(mut self, redaction: Redaction) -> Self {
+        self.redaction = Some(redaction);
+        self
+    }
+
+    /// Add an interceptor that runs around every request
+    ///
+    /// Interceptors run in the order they are added.
+    pub fn interceptor(mut self, interceptor: impl Interceptor + 'static) -> Self {
+        self.interceptors.push(Arc::new(interceptor));
+        self
+    }
+
     /// Build the Config
     pub fn build(self) -> Result<Config> {
         let publishable_key = self.publishable_key
@@ -66,16 +257,51 @@ impl ConfigBuilder {
         let site_uuid = self.site_uuid
             .ok_or_else(|| Error::InvalidConfig("site UUID is required".into()))?;
 
+        if let Some(quota) = self.rate_limit {
+            if quota.requests == 0 || quota.per.is_zero() {
+                return Err(Error::InvalidConfig("rate limit must allow at least one request per non-zero period".into()));
+            }
+        }
+
+        if let Some(Redaction::Hash { key }) = &self.redaction {
+            if key.expose_secret().is_empty() {
+                return Err(Error::InvalidConfig("hash redaction requires a non-empty key".into()));
+            }
+        }
+
+        let mut retry_policy = self.retry_policy.unwrap_or_default();
+        if let Some(max_retries) = self.max_retries {
+            retry_policy.max_retries = max_retries;
+        }
+
         Ok(Config {
             publishable_key,
             secret_key,
             site_uuid,
             timeout: self.timeout.unwrap_or(Duration::from_secs(30)),
             base_url: self.base_url.unwrap_or_else(|| "https://app.bentonow.com/api/v1".into()),
+            retry_policy,
+            rate_limit: self.rate_limit,
+            redaction: self.redaction.unwrap_or_default(),
+            interceptors: self.interceptors,
         })
     }
 }
 
+fn parse_var<T: FromStr>(name: &str, value: Option<String>) -> Result<Option<T>> {
+    value
+        .map(|value| {
+            value.parse().map_err(|_| {
+                Error::InvalidConfig(format!("environment variable {} must be a non-negative integer, got '{}'", name, value))
+            })
+        })
+        .transpose()
+}
+
+fn per_second(requests: u32) -> Quota {
+    Quota { requests, per: Duration::from_secs(1) }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -92,11 +318,25 @@ mod tests {
         assert!(config.is_ok());
         let config = config.unwrap();
         assert_eq!(config.publishable_key, "pub_key");
-        assert_eq!(config.secret_key, "secret_key");
+        assert_eq!(config.secret_key.expose_secret(), "secret_key");
         assert_eq!(config.site_uuid, "site_123");
         assert_eq!(config.timeout, Duration::from_secs(60));
     }
 
+    #[test]
+    fn test_config_debug_redacts_secret_key() {
+        let builder = ConfigBuilder::new()
+            .publishable_key("pub_key")
+            .secret_key("super_secret_value")
+            .site_uuid("site_123");
+        assert!(!format!("{:?}", builder).contains("super_secret_value"));
+
+        let config = builder.build().unwrap();
+        let debug = format!("{:?}", config);
+        assert!(!debug.contains("super_secret_value"), "{}", debug);
+        assert!(debug.contains("REDACTED"), "{}", debug);
+    }
+
     #[test]
     fn test_config_builder_missing_required() {
         let config = ConfigBuilder::new().build();
@@ -125,5 +365,169 @@ mod tests {
 
         assert_eq!(config.timeout, Duration::from_secs(30));
         assert_eq!(config.base_url, "https://app.bentonow.com/api/v1");
+        assert_eq!(config.retry_policy, RetryPolicy::default());
+        assert_eq!(config.rate_limit, None);
+        assert_eq!(config.redaction, Redaction::Mask);
+    }
+
+    #[test]
+    fn test_config_builder_retry_policy() {
+        let policy = RetryPolicy::new()
+            .max_retries(5)
+            .base_delay(Duration::from_millis(250))
+            .retry_non_idempotent(true);
+
+        let config = ConfigBuilder::new()
+            .publishable_key("pub_key")
+            .secret_key("secret_key")
+            .site_uuid("site_123")
+            .retry_policy(policy.clone())
+            .build()
+            .unwrap();
+        assert_eq!(config.retry_policy, policy);
+
+        let config = ConfigBuilder::new()
+            .publishable_key("pub_key")
+            .secret_key("secret_key")
+            .site_uuid("site_123")
+            .retry_policy(policy)
+            .max_retries(1)
+            .build()
+            .unwrap();
+        assert_eq!(config.retry_policy.max_retries, 1);
+        assert!(config.retry_policy.retry_non_idempotent);
+    }
+
+    #[test]
+    fn test_config_builder_rate_limit() {
+        let config = ConfigBuilder::new()
+            .publishable_key("pub_key")
+            .secret_key("secret_key")
+            .site_uuid("site_123")
+            .rate_limit(10, Duration::from_secs(1))
+            .build()
+            .unwrap();
+        assert_eq!(config.rate_limit, Some(Quota { requests: 10, per: Duration::from_secs(1) }));
+
+        let config = ConfigBuilder::new()
+            .publishable_key("pub_key")
+            .secret_key("secret_key")
+            .site_uuid("site_123")
+            .rate_limit(0, Duration::from_secs(1))
+            .build();
+        assert!(matches!(config, Err(Error::InvalidConfig(_))));
+    }
+
+    fn vars(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let map: HashMap<String, String> = pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        move |name| map.get(name).cloned()
+    }
+
+    const REQUIRED_VARS: [(&str, &str); 3] = [
+        ("BENTO_PUBLISHABLE_KEY", "pub_key"),
+        ("BENTO_SECRET_KEY", "secret_key"),
+        ("BENTO_SITE_UUID", "site_123"),
+    ];
+
+    #[test]
+    fn test_config_from_env() {
+        let mut pairs = REQUIRED_VARS.to_vec();
+        pairs.extend([
+            ("BENTO_TIMEOUT", "10"),
+            ("BENTO_BASE_URL", "https://staging.example.com/api/v1"),
+            ("BENTO_MAX_RETRIES", "1"),
+            ("BENTO_RATE_LIMIT", "5"),
+            ("BENTO_REDACTION", "hash"),
+            ("BENTO_REDACTION_KEY", "test-key"),
+        ]);
+
+        let config = ConfigBuilder::from_vars(vars(&pairs)).unwrap().build().unwrap();
+        assert_eq!(config.publishable_key, "pub_key");
+        assert_eq!(config.site_uuid, "site_123");
+        assert_eq!(config.timeout, Duration::from_secs(10));
+        assert_eq!(config.base_url, "https://staging.example.com/api/v1");
+        assert_eq!(config.retry_policy.max_retries, 1);
+        assert_eq!(config.rate_limit, Some(Quota { requests: 5, per: Duration::from_secs(1) }));
+        assert_eq!(config.redaction, Redaction::hash("test-key"));
+
+        let config = ConfigBuilder::from_vars(vars(&REQUIRED_VARS)).unwrap().build().unwrap();
+        assert_eq!(config.timeout, Duration::from_secs(30));
+        assert_eq!(config.rate_limit, None);
+    }
+
+    #[test]
+    fn test_config_from_env_errors() {
+        let result = ConfigBuilder::from_vars(vars(&[("BENTO_PUBLISHABLE_KEY", "pub_key"), ("BENTO_SECRET_KEY", "")]));
+        match result {
+            Err(Error::InvalidConfig(message)) => assert!(message.contains("BENTO_SECRET_KEY"), "{}", message),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let mut pairs = REQUIRED_VARS.to_vec();
+        pairs.push(("BENTO_TIMEOUT", "30s"));
+        match ConfigBuilder::from_vars(vars(&pairs)) {
+            Err(Error::InvalidConfig(message)) => assert!(message.contains("BENTO_TIMEOUT"), "{}", message),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        for value in ["off", "hash"] {
+            let mut pairs = REQUIRED_VARS.to_vec();
+            pairs.push(("BENTO_REDACTION", value));
+            match ConfigBuilder::from_vars(vars(&pairs)) {
+                Err(Error::InvalidConfig(message)) => assert!(message.contains("BENTO_REDACTION"), "{}", message),
+                other => panic!("unexpected result: {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_config_from_file() {
+        let path = std::env::temp_dir().join(format!("bento-config-test-{}.toml", std::process::id()));
+        fs::write(&path, r#"
+            [prod]
+            publishable_key = "pub_key"
+            secret_key = "secret_key"
+            site_uuid = "site_123"
+
+            [staging]
+            publishable_key = "pub_key"
+            secret_key = "secret_key"
+            site_uuid = "site_456"
+            base_url = "https://staging.example.com/api/v1"
+            timeout = 5
+            rate_limit = 2
+            redaction = "keep"
+
+            [broken]
+            publishable_key = "pub_key"
+            site_uuid = "site_789"
+
+            [typo]
+            publishable_key = "pub_key"
+            secret_key = "secret_key"
+            site_uuid = "site_789"
+            time_out = 5
+        "#).unwrap();
+
+        let config = ConfigBuilder::from_file(&path, "prod").unwrap().build().unwrap();
+        assert_eq!(config.site_uuid, "site_123");
+        assert_eq!(config.secret_key.expose_secret(), "secret_key");
+        assert_eq!(config.base_url, "https://app.bentonow.com/api/v1");
+
+        let config = ConfigBuilder::from_file(&path, "staging").unwrap().build().unwrap();
+        assert_eq!(config.base_url, "https://staging.example.com/api/v1");
+        assert_eq!(config.timeout, Duration::from_secs(5));
+        assert_eq!(config.rate_limit, Some(Quota { requests: 2, per: Duration::from_secs(1) }));
+        assert_eq!(config.redaction, Redaction::Keep);
+
+        for (profile, expected) in [("dev", "profile 'dev' not found"), ("broken", "missing secret_key"), ("typo", "time_out")] {
+            match ConfigBuilder::from_file(&path, profile) {
+                Err(Error::InvalidConfig(message)) => assert!(message.contains(expected), "{}", message),
+                other => panic!("unexpected result for {}: {:?}", profile, other),
+            }
+        }
+
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(ConfigBuilder::from_file(&path, "prod"), Err(Error::InvalidConfig(_))));
     }
 }
\ No newline at end of file
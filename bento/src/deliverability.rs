@@ -0,0 +1,511 @@
+//! Blacklist monitoring for sending domains and IPs
+//!
+//! [`DeliverabilityMonitor`] checks a set of domains and IP addresses with
+//! [`Client::get_blacklist_status`] on a schedule, compares each result with the
+//! previous one, and reports every blacklist that started or stopped listing a
+//! target as a [`DeliverabilityEvent`].
+
+use crate::experimental::{BlacklistData, BlacklistStatus};
+use crate::{Client, Error, Result};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{error, info, warn};
+
+/// Callback invoked with every change
+pub type ChangeHandler = Arc<dyn Fn(&DeliverabilityEvent) + Send + Sync>;
+
+/// A sending domain or IP address to monitor
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum MonitorTarget {
+    /// A sending domain, e.g. `mail.example.com`
+    Domain(String),
+    /// A sending IP address
+    Ip(IpAddr),
+}
+
+impl MonitorTarget {
+    fn blacklist_data(&self) -> BlacklistData {
+        match self {
+            MonitorTarget::Domain(domain) => BlacklistData { domain: Some(domain.clone()), ip: None },
+            MonitorTarget::Ip(ip) => BlacklistData { domain: None, ip: Some(ip.to_string()) },
+        }
+    }
+}
+
+impl fmt::Display for MonitorTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MonitorTarget::Domain(domain) => f.write_str(domain),
+            MonitorTarget::Ip(ip) => write!(f, "{}", ip),
+        }
+    }
+}
+
+/// A change in the listing status of a target
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeliverabilityEvent {
+    /// A blacklist started listing the target
+    Listed {
+        /// Domain or IP address that was listed
+        target: MonitorTarget,
+        /// Name of the blacklist, as reported by the API
+        blacklist: String,
+    },
+    /// A blacklist stopped listing the target
+    Delisted {
+        /// Domain or IP address that was delisted
+        target: MonitorTarget,
+        /// Name of the blacklist, as reported by the API
+        blacklist: String,
+    },
+}
+
+impl DeliverabilityEvent {
+    /// Get the target the event is about
+    pub fn target(&self) -> &MonitorTarget {
+        match self {
+            DeliverabilityEvent::Listed { target, .. } | DeliverabilityEvent::Delisted { target, .. } => target,
+        }
+    }
+
+    /// Get the blacklist that changed
+    pub fn blacklist(&self) -> &str {
+        match self {
+            DeliverabilityEvent::Listed { blacklist, .. } | DeliverabilityEvent::Delisted { blacklist, .. } => blacklist,
+        }
+    }
+}
+
+/// Checks targets against blacklists and reports listing changes
+///
+/// The first check of a target compares against an empty result, so listings that
+/// already exist when the monitor starts are reported as [`DeliverabilityEvent::Listed`].
+/// A failed check is logged and leaves the previous result in place.
+///
+/// # Example
+/// ```no_run
+/// # async fn example(client: bento::Client) -> Result<(), Box<dyn std::error::Error>> {
+/// use bento::deliverability::{DeliverabilityEvent, DeliverabilityMonitor};
+/// use std::time::Duration;
+///
+/// let (sender, mut changes) = tokio::sync::mpsc::channel(16);
+/// let monitor = DeliverabilityMonitor::builder(client)
+///     .domain("mail.example.com")
+///     .ip("203.0.113.7".parse().unwrap())
+///     .interval(Duration::from_secs(15 * 60))
+///     .notify(sender)
+///     .build()?
+///     .spawn();
+///
+/// let alerts = tokio::spawn(async move {
+///     while let Some(change) = changes.recv().await {
+///         if let DeliverabilityEvent::Listed { target, blacklist } = change {
+///             eprintln!("{} was listed on {}", target, blacklist);
+///         }
+///     }
+/// });
+///
+/// tokio::signal::ctrl_c().await?;
+/// // Dropping the stopped monitor closes the channel, which ends the alerts task
+/// monitor.stop().await;
+/// alerts.await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct DeliverabilityMonitor {
+    client: Client,
+    targets: Vec<MonitorTarget>,
+    interval: Duration,
+    on_change: Vec<ChangeHandler>,
+    sender: Option<mpsc::Sender<DeliverabilityEvent>>,
+    undelivered: Vec<DeliverabilityEvent>,
+    previous: HashMap<MonitorTarget, BlacklistStatus>,
+}
+
+impl fmt::Debug for DeliverabilityMonitor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeliverabilityMonitor")
+            .field("targets", &self.targets)
+            .field("interval", &self.interval)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DeliverabilityMonitor {
+    /// Create a builder for a monitor that checks through `client`
+    pub fn builder(client: Client) -> DeliverabilityMonitorBuilder {
+        DeliverabilityMonitorBuilder {
+            client,
+            targets: Vec::new(),
+            interval: Duration::from_secs(60 * 60),
+            on_change: Vec::new(),
+            sender: None,
+        }
+    }
+
+    /// Check every target once, report the changes and return them
+    pub async fn check(&mut self) -> Vec<DeliverabilityEvent> {
+        let mut events = Vec::new();
+
+        for target in &self.targets {
+            let status = match self.client.get_blacklist_status(&target.blacklist_data()).await {
+                Ok(status) => status,
+                Err(err) => {
+                    warn!(error = %err, %target, "blacklist check failed");
+                    continue;
+                }
+            };
+
+            let before = self.previous.get(target).map(listings).unwrap_or_default();
+            let after = listings(&status);
+            events.extend(after.difference(&before).map(|blacklist| DeliverabilityEvent::Listed {
+                target: target.clone(),
+                blacklist: blacklist.to_string(),
+            }));
+            events.extend(before.difference(&after).map(|blacklist| DeliverabilityEvent::Delisted {
+                target: target.clone(),
+                blacklist: blacklist.to_string(),
+            }));
+
+            self.previous.insert(target.clone(), status);
+        }
+
+        for event in &events {
+            self.dispatch(event);
+        }
+        if self.sender.is_some() {
+            self.undelivered.extend(events.iter().cloned());
+            self.send_undelivered();
+        }
+        events
+    }
+
+    /// Get the changes that did not fit in the [`notify`](DeliverabilityMonitorBuilder::notify)
+    /// channel yet, oldest first
+    ///
+    /// They are sent again, in order, before the changes of the next check.
+    pub fn undelivered(&self) -> &[DeliverabilityEvent] {
+        &self.undelivered
+    }
+
+    /// Get the result of the latest successful check of a target
+    pub fn previous(&self, target: &MonitorTarget) -> Option<&BlacklistStatus> {
+        self.previous.get(target)
+    }
+
+    /// Run [`check`](Self::check) immediately and then on every interval in a background task
+    ///
+    /// Must be called from within a tokio runtime.
+    pub fn spawn(mut self) -> MonitorHandle {
+        let (stop, mut stopped) = oneshot::channel();
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.interval);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    _ = &mut stopped => break,
+                    _ = interval.tick() => {
+                        self.check().await;
+                    }
+                }
+            }
+            self
+        });
+
+        MonitorHandle { stop, task }
+    }
+
+    fn dispatch(&self, event: &DeliverabilityEvent) {
+        match event {
+            DeliverabilityEvent::Listed { target, blacklist } => warn!(%target, blacklist, "target was listed"),
+            DeliverabilityEvent::Delisted { target, blacklist } => info!(%target, blacklist, "target was delisted"),
+        }
+        for handler in &self.on_change {
+            handler(event);
+        }
+    }
+
+    /// Send queued changes to the channel without waiting for capacity, so a slow
+    /// receiver cannot hold up checks or `stop`
+    fn send_undelivered(&mut self) {
+        let Some(sender) = &self.sender else { return };
+
+        let mut sent = 0;
+        for event in &self.undelivered {
+            match sender.try_send(event.clone()) {
+                Ok(()) => sent += 1,
+                Err(TrySendError::Full(_)) => break,
+                // A dropped receiver only means nobody is listening on the channel
+                Err(TrySendError::Closed(_)) => {
+                    sent = self.undelivered.len();
+                    break;
+                }
+            }
+        }
+        self.undelivered.drain(..sent);
+
+        if !self.undelivered.is_empty() {
+            warn!(count = self.undelivered.len(), "deliverability channel is full, keeping events for the next check");
+        }
+    }
+}
+
+/// Names of the blacklists listing the status's target
+fn listings(status: &BlacklistStatus) -> BTreeSet<&str> {
+    status.listed_on().into_iter().collect()
+}
+
+/// Handle to a monitor running in the background
+#[derive(Debug)]
+pub struct MonitorHandle {
+    stop: oneshot::Sender<()>,
+    task: JoinHandle<DeliverabilityMonitor>,
+}
+
+impl MonitorHandle {
+    /// Stop the monitor after any check in progress and return it
+    ///
+    /// Returns `None` if the background task panicked.
+    pub async fn stop(self) -> Option<DeliverabilityMonitor> {
+        let _ = self.stop.send(());
+        match self.task.await {
+            Ok(monitor) => Some(monitor),
+            Err(e) => {
+                error!(error = %e, "deliverability monitor task failed");
+                None
+            }
+        }
+    }
+}
+
+/// Builder for [`DeliverabilityMonitor`]
+pub struct DeliverabilityMonitorBuilder {
+    client: Client,
+    targets: Vec<MonitorTarget>,
+    interval: Duration,
+    on_change: Vec<ChangeHandler>,
+    sender: Option<mpsc::Sender<DeliverabilityEvent>>,
+}
+
+impl fmt::Debug for DeliverabilityMonitorBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeliverabilityMonitorBuilder")
+            .field("targets", &self.targets)
+            .field("interval", &self.interval)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DeliverabilityMonitorBuilder {
+    /// Add a sending domain to monitor
+    pub fn domain(mut self, domain: impl Into<String>) -> Self {
+        self.targets.push(MonitorTarget::Domain(domain.into()));
+        self
+    }
+
+    /// Add a sending IP address to monitor
+    pub fn ip(mut self, ip: IpAddr) -> Self {
+        self.targets.push(MonitorTarget::Ip(ip));
+        self
+    }
+
+    /// Set the time between checks (default 1h)
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Add a callback invoked with every change
+    pub fn on_change<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&DeliverabilityEvent) + Send + Sync + 'static,
+    {
+        self.on_change.push(Arc::new(handler));
+        self
+    }
+
+    /// Send every change to a channel
+    ///
+    /// The monitor never waits for capacity. Changes that do not fit are kept, see
+    /// [`DeliverabilityMonitor::undelivered`], and sent before those of the next check.
+    pub fn notify(mut self, sender: mpsc::Sender<DeliverabilityEvent>) -> Self {
+        self.sender = Some(sender);
+        self
+    }
+
+    /// Build the monitor
+    ///
+    /// # Errors
+    /// * `Error::InvalidRequest` if a domain is empty
+    /// * `Error::InvalidConfig` if there are no targets or the interval is zero
+    pub fn build(mut self) -> Result<DeliverabilityMonitor> {
+        if self.targets.iter().any(|t| matches!(t, MonitorTarget::Domain(d) if d.is_empty())) {
+            return Err(Error::InvalidRequest("Domain is required".into()));
+        }
+        if self.targets.is_empty() {
+            return Err(Error::InvalidConfig("at least one domain or IP must be monitored".into()));
+        }
+        if self.interval.is_zero() {
+            return Err(Error::InvalidConfig("check interval must be positive".into()));
+        }
+        self.targets.sort();
+        self.targets.dedup();
+
+        Ok(DeliverabilityMonitor {
+            client: self.client,
+            targets: self.targets,
+            interval: self.interval,
+            on_change: self.on_change,
+            sender: self.sender,
+            undelivered: Vec::new(),
+            previous: HashMap::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::FakeBento;
+    use crate::{HttpResponse, Method};
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    fn stub_results(bento: &FakeBento, results: serde_json::Value) {
+        bento.stub(
+            Method::Get,
+            "/experimental/blacklist.json",
+            HttpResponse::json_body(200, &json!({ "results": results })).unwrap(),
+        );
+    }
+
+    fn domain() -> MonitorTarget {
+        MonitorTarget::Domain("mail.example.com".into())
+    }
+
+    #[tokio::test]
+    async fn test_reports_listing_changes() {
+        let bento = FakeBento::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_events = Arc::clone(&seen);
+        let (sender, mut receiver) = mpsc::channel(8);
+
+        let mut monitor = DeliverabilityMonitor::builder(bento.client())
+            .domain("mail.example.com")
+            .on_change(move |event| seen_events.lock().unwrap().push(event.clone()))
+            .notify(sender)
+            .build()
+            .unwrap();
+
+        stub_results(&bento, json!({ "spamhaus": true, "spamcop": false }));
+        let events = monitor.check().await;
+        assert_eq!(events, vec![DeliverabilityEvent::Listed { target: domain(), blacklist: "spamhaus".into() }]);
+        assert!(monitor.previous(&domain()).unwrap().is_listed());
+
+        // Unchanged listings produce no events
+        assert!(monitor.check().await.is_empty());
+
+        stub_results(&bento, json!({ "spamhaus": false, "spamcop": true }));
+        let events = monitor.check().await;
+        assert_eq!(events, vec![
+            DeliverabilityEvent::Listed { target: domain(), blacklist: "spamcop".into() },
+            DeliverabilityEvent::Delisted { target: domain(), blacklist: "spamhaus".into() },
+        ]);
+
+        assert_eq!(seen.lock().unwrap().len(), 3);
+        assert_eq!(receiver.recv().await.unwrap().blacklist(), "spamhaus");
+        assert_eq!(receiver.recv().await.unwrap().blacklist(), "spamcop");
+    }
+
+    #[tokio::test]
+    async fn test_full_channel_keeps_events() {
+        let bento = FakeBento::new();
+        let (sender, mut receiver) = mpsc::channel(1);
+        let mut monitor = DeliverabilityMonitor::builder(bento.client())
+            .domain("mail.example.com")
+            .notify(sender)
+            .build()
+            .unwrap();
+
+        stub_results(&bento, json!({ "spamhaus": true, "spamcop": true }));
+        assert_eq!(monitor.check().await.len(), 2);
+        assert_eq!(monitor.undelivered().len(), 1);
+
+        assert_eq!(receiver.recv().await.unwrap().blacklist(), "spamcop");
+        assert!(receiver.try_recv().is_err());
+
+        // The change that did not fit is sent by the next check, ahead of new changes
+        stub_results(&bento, json!({ "spamhaus": true, "spamcop": false }));
+        assert_eq!(monitor.check().await.len(), 1);
+        assert_eq!(monitor.undelivered().len(), 1);
+        assert_eq!(receiver.recv().await.unwrap(), DeliverabilityEvent::Listed { target: domain(), blacklist: "spamhaus".into() });
+
+        assert!(monitor.check().await.is_empty());
+        assert!(monitor.undelivered().is_empty());
+        assert_eq!(receiver.recv().await.unwrap(), DeliverabilityEvent::Delisted { target: domain(), blacklist: "spamcop".into() });
+    }
+
+    #[tokio::test]
+    async fn test_failed_check_keeps_previous_result() {
+        let bento = FakeBento::new();
+        let mut monitor = DeliverabilityMonitor::builder(bento.client())
+            .ip("203.0.113.7".parse().unwrap())
+            .build()
+            .unwrap();
+        let target = MonitorTarget::Ip("203.0.113.7".parse().unwrap());
+
+        stub_results(&bento, json!({ "barracuda": true }));
+        assert_eq!(monitor.check().await.len(), 1);
+
+        bento.fail_next(503);
+        assert!(monitor.check().await.is_empty());
+        assert!(monitor.previous(&target).unwrap().is_listed());
+        assert_eq!(bento.requests().last().unwrap().path(), "/api/v1/experimental/blacklist.json");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_spawn_checks_on_interval() {
+        let bento = FakeBento::new();
+        let (sender, mut receiver) = mpsc::channel(8);
+        stub_results(&bento, json!({ "spamhaus": true }));
+
+        let handle = DeliverabilityMonitor::builder(bento.client())
+            .domain("mail.example.com")
+            .interval(Duration::from_secs(60))
+            .notify(sender)
+            .build()
+            .unwrap()
+            .spawn();
+
+        let event = receiver.recv().await.unwrap();
+        assert!(matches!(event, DeliverabilityEvent::Listed { .. }));
+
+        stub_results(&bento, json!({ "spamhaus": false }));
+        let event = receiver.recv().await.unwrap();
+        assert!(matches!(event, DeliverabilityEvent::Delisted { .. }));
+
+        let monitor = handle.stop().await.unwrap();
+        assert!(!monitor.previous(&domain()).unwrap().is_listed());
+    }
+
+    #[test]
+    fn test_builder_validation() {
+        let client = FakeBento::new().client();
+
+        let result = DeliverabilityMonitor::builder(client.clone()).build();
+        assert!(matches!(result, Err(Error::InvalidConfig(_))));
+
+        let result = DeliverabilityMonitor::builder(client.clone()).domain("").build();
+        assert!(matches!(result, Err(Error::InvalidRequest(_))));
+
+        let result = DeliverabilityMonitor::builder(client).domain("example.com").interval(Duration::ZERO).build();
+        assert!(matches!(result, Err(Error::InvalidConfig(_))));
+    }
+}
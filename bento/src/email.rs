@@ -1,10 +1,14 @@
 use serde::{Deserialize, Serialize};
-use crate::{EmailData, Error};
+use crate::{BatchOutcome, Client, EmailData, Error, Result};
+use tracing::instrument;
+
+/// Maximum number of emails accepted by `/batch/emails` in one request
+pub const MAX_BATCH_SIZE: usize = 60;
 
 /// Represents a batch of email messages for processing.
 ///
 /// The `EmailBatch` struct contains a collection of email messages (`EmailData`),
-/// with a restriction on the maximum number of emails allowed in a single batch (60).
+/// with a restriction on the maximum number of emails allowed in a single batch ([`MAX_BATCH_SIZE`]).
 ///
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,9 +23,9 @@ impl EmailBatch {
     /// # Errors
     /// Returns an error if the batch size exceeds 60 emails
     pub fn new(emails: Vec<EmailData>) -> crate::Result<Self> {
-        if emails.len() > 60 {
+        if emails.len() > MAX_BATCH_SIZE {
             return Err(Error::InvalidBatchSize(
-                "Maximum batch size is 60 emails".into()
+                format!("Maximum batch size is {} emails", MAX_BATCH_SIZE)
             ));
         }
         Ok(Self { emails })
@@ -32,9 +36,9 @@ impl EmailBatch {
     /// # Errors
     /// Returns an error if adding would exceed the maximum batch size
     pub fn add_email(&mut self, email: EmailData) -> crate::Result<()> {
-        if self.emails.len() >= 60 {
+        if self.emails.len() >= MAX_BATCH_SIZE {
             return Err(Error::InvalidBatchSize(
-                "Maximum batch size is 60 emails".into()
+                format!("Maximum batch size is {} emails", MAX_BATCH_SIZE)
             ));
         }
         self.emails.push(email);
@@ -52,15 +56,72 @@ impl EmailBatch {
     }
 }
 
+impl Client {
+    /// Send a batch of transactional emails
+    ///
+    /// # Arguments
+    /// * `batch` - Batch of up to 60 emails to send
+    ///
+    /// # Returns
+    /// * `Result<BatchOutcome>` - Number of accepted and rejected emails
+    ///
+    /// # Errors
+    /// * `Error::InvalidRequest` if the batch is empty
+    /// * `Error::InvalidBatchSize` if the batch has more than 60 emails
+    /// * `Error::InvalidRequest` if any subject or body is empty
+    /// * `Error::Api` if the API returns an error status
+    #[instrument(skip_all, fields(count = batch.len()))]
+    pub async fn send_emails(&self, batch: EmailBatch) -> Result<BatchOutcome> {
+        if batch.is_empty() {
+            return Err(Error::InvalidRequest("No emails provided".into()));
+        }
+        // `emails` is public, so the limit enforced by `EmailBatch::new` can be bypassed
+        if batch.len() > MAX_BATCH_SIZE {
+            return Err(Error::InvalidBatchSize(
+                format!("Maximum batch size is {} emails", MAX_BATCH_SIZE)
+            ));
+        }
+
+        for email in &batch.emails {
+            if email.subject.is_empty() {
+                return Err(Error::InvalidRequest("Subject is required".into()));
+            }
+            if email.html_body.is_empty() {
+                return Err(Error::InvalidRequest("HTML body is required".into()));
+            }
+        }
+
+        let url = self.build_url("/batch/emails")?;
+        let response = self.request(
+            self.post(&url)
+                .json(&batch)
+        ).await?;
+
+        let outcome: BatchOutcome = response.json()?;
+        Ok(outcome)
+    }
+
+    /// Send a single transactional email
+    ///
+    /// This is a convenience wrapper around [`Client::send_emails`] with a batch of one.
+    #[instrument(skip_all)]
+    pub async fn send_email(&self, email: EmailData) -> Result<BatchOutcome> {
+        let batch = EmailBatch::new(vec![email])?;
+        self.send_emails(batch).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+    use wiremock::matchers::{method, path, body_json};
 
     #[test]
     fn test_email_batch_creation() {
         let emails = vec![EmailData {
-            to: "test@example.com".into(),
-            from: "sender@example.com".into(),
+            to: "test@example.com".parse().unwrap(),
+            from: "sender@example.com".parse().unwrap(),
             subject: "Test".into(),
             html_body: "<p>Test</p>".into(),
             transactional: true,
@@ -74,9 +135,9 @@ mod tests {
 
     #[test]
     fn test_email_batch_size_limit() {
-        let emails = (0..61).map(|_| EmailData {
-            to: "test@example.com".into(),
-            from: "sender@example.com".into(),
+        let emails = (0..=MAX_BATCH_SIZE).map(|_| EmailData {
+            to: "test@example.com".parse().unwrap(),
+            from: "sender@example.com".parse().unwrap(),
             subject: "Test".into(),
             html_body: "<p>Test</p>".into(),
             transactional: true,
@@ -87,4 +148,109 @@ mod tests {
         assert!(batch.is_err());
         assert!(matches!(batch.unwrap_err(), Error::InvalidBatchSize(_)));
     }
-}
+
+    #[tokio::test]
+    async fn test_send_emails() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/batch/emails"))
+            .and(body_json(serde_json::json!({
+                "emails": [{
+                    "to": "test@example.com",
+                    "from": "sender@example.com",
+                    "subject": "Test",
+                    "html_body": "<p>Test</p>",
+                    "transactional": true
+                }]
+            })))
+            .respond_with(ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({
+                    "results": 1
+                })))
+            .mount(&mock_server)
+            .await;
+
+        let client = crate::test_utils::create_test_client(mock_server.uri());
+
+        let email = EmailData {
+            to: "test@example.com".parse().unwrap(),
+            from: "sender@example.com".parse().unwrap(),
+            subject: "Test".into(),
+            html_body: "<p>Test</p>".into(),
+            transactional: true,
+            personalizations: None,
+        };
+
+        let result = client.send_email(email).await;
+        assert!(result.is_ok(), "Expected OK, got {:?}", result);
+        let outcome = result.unwrap();
+        assert_eq!(outcome.succeeded, 1);
+        assert_eq!(outcome.failed, 0);
+    }
+
+    #[tokio::test]
+    async fn test_send_emails_reports_rejections() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/batch/emails"))
+            .respond_with(ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({
+                    "results": 1,
+                    "failed": 1
+                })))
+            .mount(&mock_server)
+            .await;
+
+        let client = crate::test_utils::create_test_client(mock_server.uri());
+
+        let emails = (0..2).map(|_| EmailData {
+            to: "test@example.com".parse().unwrap(),
+            from: "sender@example.com".parse().unwrap(),
+            subject: "Test".into(),
+            html_body: "<p>Test</p>".into(),
+            transactional: true,
+            personalizations: None,
+        }).collect();
+
+        let result = client.send_emails(EmailBatch::new(emails).unwrap()).await;
+        let outcome = result.unwrap();
+        assert_eq!(outcome.succeeded, 1);
+        assert_eq!(outcome.failed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_send_emails_validation() {
+        let mock_server = MockServer::start().await;
+        let client = crate::test_utils::create_test_client(mock_server.uri());
+
+        // Test empty batch
+        let result = client.send_emails(EmailBatch::new(vec![]).unwrap()).await;
+        assert!(matches!(result, Err(Error::InvalidRequest(_))));
+
+        // Test empty subject
+        let email = EmailData {
+            to: "test@example.com".parse().unwrap(),
+            from: "sender@example.com".parse().unwrap(),
+            subject: "".into(),
+            html_body: "<p>Test</p>".into(),
+            transactional: true,
+            personalizations: None,
+        };
+        let result = client.send_email(email).await;
+        assert!(matches!(result, Err(Error::InvalidRequest(_))));
+
+        // Test oversized batch built through the public field
+        let emails = (0..=MAX_BATCH_SIZE).map(|_| EmailData {
+            to: "test@example.com".parse().unwrap(),
+            from: "sender@example.com".parse().unwrap(),
+            subject: "Test".into(),
+            html_body: "<p>Test</p>".into(),
+            transactional: true,
+            personalizations: None,
+        }).collect();
+        let result = client.send_emails(EmailBatch { emails }).await;
+        assert!(matches!(result, Err(Error::InvalidBatchSize(_))));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,309 @@
+//! Validated email address type.
+
+use crate::{Error, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+/// Maximum length of a full address (RFC 5321 path limit minus the angle brackets)
+const MAX_ADDRESS_LENGTH: usize = 254;
+/// Maximum length of the local part
+const MAX_LOCAL_LENGTH: usize = 64;
+/// Maximum length of a single domain label
+const MAX_LABEL_LENGTH: usize = 63;
+
+/// A syntactically valid email address
+///
+/// Addresses are validated against the RFC 5322 `addr-spec` grammar when they are
+/// constructed, so an `EmailAddress` value is always well formed. The domain is
+/// lowercased; the local part is kept as given because it is case-sensitive.
+///
+/// Supported forms:
+/// * dot-atom local parts (`first.last+tag@example.com`)
+/// * quoted local parts (`"john doe"@example.com`)
+/// * domain names with at least two labels, including internationalized names
+/// * address literals (`user@[192.0.2.1]`, `user@[IPv6:2001:db8::1]`)
+///
+/// Comments, folding whitespace and single-label domains such as `localhost` are
+/// rejected since Bento cannot deliver to them.
+///
+/// # Example
+/// ```
+/// use bento::EmailAddress;
+///
+/// let email: EmailAddress = "Jane.Doe@Example.COM".parse()?;
+/// assert_eq!(email.as_str(), "Jane.Doe@example.com");
+/// assert_eq!(email.domain(), "example.com");
+///
+/// assert!("x y@example.com".parse::<EmailAddress>().is_err());
+/// # Ok::<(), bento::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EmailAddress {
+    address: String,
+    at: usize,
+}
+
+impl EmailAddress {
+    /// Parse and normalize an email address
+    ///
+    /// Surrounding whitespace is ignored.
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidEmail` if the address is not syntactically valid.
+    pub fn parse(input: &str) -> Result<Self> {
+        let trimmed = input.trim();
+        let invalid = || Error::InvalidEmail(input.to_string());
+
+        if trimmed.len() > MAX_ADDRESS_LENGTH {
+            return Err(invalid());
+        }
+
+        let at = trimmed.rfind('@').ok_or_else(invalid)?;
+        let (local, domain) = (&trimmed[..at], &trimmed[at + 1..]);
+
+        if !is_valid_local_part(local) {
+            return Err(invalid());
+        }
+        let domain = normalize_domain(domain).ok_or_else(invalid)?;
+
+        Ok(Self {
+            address: format!("{}@{}", local, domain),
+            at,
+        })
+    }
+
+    /// Get the address as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.address
+    }
+
+    /// Get the part before the `@`
+    pub fn local_part(&self) -> &str {
+        &self.address[..self.at]
+    }
+
+    /// Get the lowercased part after the `@`
+    pub fn domain(&self) -> &str {
+        &self.address[self.at + 1..]
+    }
+
+    /// Consume the address and return the underlying string
+    pub fn into_inner(self) -> String {
+        self.address
+    }
+}
+
+fn is_atext(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~".contains(c)
+}
+
+fn is_valid_local_part(local: &str) -> bool {
+    if local.is_empty() || local.len() > MAX_LOCAL_LENGTH {
+        return false;
+    }
+
+    if let Some(quoted) = local.strip_prefix('"').and_then(|l| l.strip_suffix('"')) {
+        return is_valid_quoted_content(quoted);
+    }
+
+    local.split('.').all(|atom| !atom.is_empty() && atom.chars().all(is_atext))
+}
+
+fn is_valid_quoted_content(content: &str) -> bool {
+    let mut chars = content.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(escaped) if escaped == ' ' || escaped.is_ascii_graphic() => {}
+                _ => return false,
+            },
+            '"' => return false,
+            c if c == ' ' || c.is_ascii_graphic() => {}
+            _ => return false,
+        }
+    }
+    true
+}
+
+fn normalize_domain(domain: &str) -> Option<String> {
+    if let Some(literal) = domain.strip_prefix('[').and_then(|d| d.strip_suffix(']')) {
+        let valid = match literal.get(..5) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("IPv6:") => literal[5..].parse::<Ipv6Addr>().is_ok(),
+            _ => literal.parse::<Ipv4Addr>().is_ok(),
+        };
+        return valid.then(|| domain.to_lowercase().replace("[ipv6:", "[IPv6:"));
+    }
+
+    let domain = domain.to_lowercase();
+    if domain.is_empty() || domain.len() > 253 {
+        return None;
+    }
+
+    let labels: Vec<&str> = domain.split('.').collect();
+    if labels.len() < 2 {
+        return None;
+    }
+
+    let valid_labels = labels.iter().all(|label| {
+        !label.is_empty()
+            && label.chars().count() <= MAX_LABEL_LENGTH
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_alphanumeric() || c == '-')
+    });
+    let numeric_tld = labels.last().is_some_and(|tld| tld.chars().all(|c| c.is_ascii_digit()));
+
+    (valid_labels && !numeric_tld).then_some(domain)
+}
+
+impl FromStr for EmailAddress {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<&str> for EmailAddress {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        Self::parse(value)
+    }
+}
+
+impl TryFrom<String> for EmailAddress {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        Self::parse(&value)
+    }
+}
+
+impl From<EmailAddress> for String {
+    fn from(email: EmailAddress) -> Self {
+        email.address
+    }
+}
+
+impl fmt::Display for EmailAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.address)
+    }
+}
+
+impl AsRef<str> for EmailAddress {
+    fn as_ref(&self) -> &str {
+        &self.address
+    }
+}
+
+impl PartialEq<str> for EmailAddress {
+    fn eq(&self, other: &str) -> bool {
+        self.address == other
+    }
+}
+
+impl PartialEq<&str> for EmailAddress {
+    fn eq(&self, other: &&str) -> bool {
+        self.address == *other
+    }
+}
+
+impl Serialize for EmailAddress {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.address)
+    }
+}
+
+impl<'de> Deserialize<'de> for EmailAddress {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Self::parse(&value).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_addresses() {
+        for address in [
+            "test@example.com",
+            "first.last+tag@sub.example.co.uk",
+            "o'brien@example.ie",
+            "user_name-1@my-domain.io",
+            "!#$%&'*+-/=?^_`{|}~@example.com",
+            "\"john doe\"@example.com",
+            "\"a\\\"b\"@example.com",
+            "user@[192.0.2.1]",
+            "user@[IPv6:2001:db8::1]",
+            "user@bücher.de",
+        ] {
+            assert!(EmailAddress::parse(address).is_ok(), "{} should be valid", address);
+        }
+    }
+
+    #[test]
+    fn test_invalid_addresses() {
+        for address in [
+            "",
+            "@",
+            "a@",
+            "@example.com",
+            "x y@z.com",
+            "invalid-email",
+            "user@localhost",
+            "user@example",
+            ".user@example.com",
+            "user.@example.com",
+            "us..er@example.com",
+            "user@example..com",
+            "user@-example.com",
+            "user@example-.com",
+            "user@example.123",
+            "user@exa_mple.com",
+            "user@[300.0.0.1]",
+            "\"unterminated@example.com",
+            "\"a\"b\"@example.com",
+            "user@@example.com",
+        ] {
+            assert!(
+                matches!(EmailAddress::parse(address), Err(Error::InvalidEmail(_))),
+                "{} should be invalid",
+                address
+            );
+        }
+
+        let long_local = format!("{}@example.com", "a".repeat(65));
+        assert!(EmailAddress::parse(&long_local).is_err());
+
+        let long_label = format!("user@{}.com", "a".repeat(64));
+        assert!(EmailAddress::parse(&long_label).is_err());
+    }
+
+    #[test]
+    fn test_normalization() {
+        let email = EmailAddress::parse("  Jane.Doe@Example.COM ").unwrap();
+        assert_eq!(email, "Jane.Doe@example.com");
+        assert_eq!(email.local_part(), "Jane.Doe");
+        assert_eq!(email.domain(), "example.com");
+
+        assert_eq!(
+            EmailAddress::parse("user@[ipv6:2001:DB8::1]").unwrap(),
+            "user@[IPv6:2001:db8::1]"
+        );
+    }
+
+    #[test]
+    fn test_serde() {
+        let email: EmailAddress = serde_json::from_str("\"User@EXAMPLE.com\"").unwrap();
+        assert_eq!(serde_json::to_string(&email).unwrap(), "\"User@example.com\"");
+
+        let result: std::result::Result<EmailAddress, _> = serde_json::from_str("\"a@\"");
+        assert!(result.is_err());
+    }
+}
\ No newline at end of file
@@ -51,6 +51,18 @@ pub enum Error {
     #[error("HTTP client error: {0}")]
     HttpClient(#[from] reqwest::Error),
 
+    /// JSON serialization or deserialization error
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// Connection to the API could not be established
+    #[error("connection failed: {0}")]
+    Connection(String),
+
+    /// Request timed out before a response was received
+    #[error("request timed out: {0}")]
+    Timeout(String),
+
     /// Rate limit exceeded
     #[error("rate limit exceeded")]
     RateLimit,
@@ -58,7 +70,118 @@ pub enum Error {
     /// Authentication failed
     #[error("authentication failed")]
     AuthenticationFailed,
+
+    /// The API accepted a batch but rejected some of its items
+    #[error("batch partially failed: {} succeeded, {} failed", .0.succeeded, .0.failed)]
+    PartialFailure(crate::BatchOutcome),
+
+    /// Event buffer queue is at capacity
+    #[error("event buffer is full")]
+    BufferFull,
+
+    /// Event buffer has been closed
+    #[error("event buffer is closed")]
+    BufferClosed,
+
+    /// Webhook signature is missing, malformed, expired or does not match
+    #[error("invalid webhook signature: {0}")]
+    InvalidSignature(String),
+
+    /// A webhook handler returned an error
+    #[error("webhook handler failed: {0}")]
+    WebhookHandler(#[source] Box<dyn std::error::Error + Send + Sync>),
+
+    /// The API returned an error status
+    ///
+    /// The response body is not part of the displayed message since it can echo
+    /// subscriber emails or fields; read it from `message` or `body` instead.
+    #[error("API error {status} on {endpoint}{}", request_id.as_ref().map(|id| format!(" (request {})", id)).unwrap_or_default())]
+    Api {
+        /// HTTP status code
+        status: u16,
+        /// Endpoint path the request was sent to
+        endpoint: String,
+        /// Raw response body
+        message: String,
+        /// Response body parsed as JSON, if it was valid JSON
+        body: Option<serde_json::Value>,
+        /// Value of the `X-Request-Id` response header, if present
+        request_id: Option<String>,
+    },
+}
+
+impl Error {
+    /// Returns the HTTP status associated with this error, if any
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Error::Api { status, .. } => Some(*status),
+            Error::RateLimit => Some(429),
+            Error::AuthenticationFailed => Some(401),
+            Error::HttpClient(e) => e.status().map(|status| status.as_u16()),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the error is transient and the request may succeed if repeated
+    ///
+    /// This covers rate limiting, 502, 503 and 504 responses, connection failures and
+    /// timeouts.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::RateLimit | Error::Connection(_) | Error::Timeout(_) => true,
+            Error::Api { status, .. } => matches!(status, 502..=504),
+            Error::HttpClient(e) => e.is_connect() || e.is_timeout(),
+            _ => false,
+        }
+    }
+
+    /// Returns true if the API rejected the request with a 4xx status
+    pub fn is_client_error(&self) -> bool {
+        self.status().is_some_and(|status| (400..500).contains(&status))
+    }
 }
 
 /// Result type for Bento operations
-pub type Result<T> = std::result::Result<T, Error>;
\ No newline at end of file
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api_error(status: u16) -> Error {
+        Error::Api {
+            status,
+            endpoint: "/fetch/subscribers".into(),
+            message: "error".into(),
+            body: None,
+            request_id: None,
+        }
+    }
+
+    #[test]
+    fn test_status() {
+        assert_eq!(api_error(404).status(), Some(404));
+        assert_eq!(Error::RateLimit.status(), Some(429));
+        assert_eq!(Error::AuthenticationFailed.status(), Some(401));
+        assert_eq!(Error::InvalidEmail("a".into()).status(), None);
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(Error::RateLimit.is_retryable());
+        assert!(Error::Connection("refused".into()).is_retryable());
+        assert!(api_error(503).is_retryable());
+        assert!(!api_error(500).is_retryable());
+        assert!(!api_error(422).is_retryable());
+        assert!(!Error::AuthenticationFailed.is_retryable());
+    }
+
+    #[test]
+    fn test_is_client_error() {
+        assert!(api_error(422).is_client_error());
+        assert!(Error::RateLimit.is_client_error());
+        assert!(Error::AuthenticationFailed.is_client_error());
+        assert!(!api_error(503).is_client_error());
+        assert!(!Error::InvalidEmail("a".into()).is_client_error());
+    }
+}
\ No newline at end of file
@@ -1,13 +1,6 @@
-use crate::{Client, EventData, EventsRequest, Error, Result};
-use serde::Deserialize;
+use crate::{BatchOutcome, Client, EventData, EventsRequest, Error, Result};
 use tracing::instrument;
 
-#[derive(Debug, Deserialize)]
-struct EventResponse {
-    results: u32,
-    failed: u32,
-}
-
 impl Client {
     /// Track events for subscribers
     ///
@@ -15,47 +8,36 @@ impl Client {
     /// * `events` - Vector of events to track
     ///
     /// # Returns
-    /// * `Result<()>` - Success or error
+    /// * `Result<BatchOutcome>` - Number of tracked and failed events
     ///
     /// # Errors
     /// * `Error::InvalidRequest` if no events are provided
-    /// * `Error::InvalidEmail` if any email is invalid
     /// * `Error::InvalidRequest` if any event type is empty
-    /// * `Error::UnexpectedResponse` if the API returns an error
-    #[instrument(skip(self))]
-    pub async fn track_events(&self, events: Vec<EventData>) -> Result<()> {
+    /// * `Error::Api` if the API returns an error status
+    #[instrument(skip_all, fields(count = events.len()))]
+    pub async fn track_events(&self, events: Vec<EventData>) -> Result<BatchOutcome> {
         if events.is_empty() {
             return Err(Error::InvalidRequest("No events provided".into()));
         }
 
         for event in &events {
-            if !event.email.contains('@') {
-                return Err(Error::InvalidEmail(event.email.clone()));
-            }
             if event.event_type.is_empty() {
                 return Err(Error::InvalidRequest("Event type is required".into()));
             }
         }
 
         let url = self.build_url("/batch/events")?;
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_batch("/batch/events", events.len());
         let request_data = EventsRequest { events };
 
         let response = self.request(
-            self.http_client
-                .post(&url)
+            self.post(&url)
                 .json(&request_data)
         ).await?;
 
-        let event_response: EventResponse = response.json().await?;
-
-        if event_response.failed > 0 {
-            return Err(Error::UnexpectedResponse(
-                format!("Event tracking partially failed: {} succeeded, {} failed",
-                        event_response.results, event_response.failed)
-            ));
-        }
-
-        Ok(())
+        let outcome: BatchOutcome = response.json()?;
+        Ok(outcome)
     }
 }
 
@@ -90,7 +72,7 @@ mod tests {
 
         let event = EventData {
             event_type: "test_event".into(),
-            email: "test@example.com".into(),
+            email: "test@example.com".parse().unwrap(),
             fields: None,
             details: None,
         };
@@ -137,7 +119,7 @@ mod tests {
 
         let event = EventData {
             event_type: "test_event".into(),
-            email: "test@example.com".into(),
+            email: "test@example.com".parse().unwrap(),
             fields: Some(fields),
             details: Some(details),
         };
@@ -155,20 +137,20 @@ mod tests {
         let result = client.track_events(vec![]).await;
         assert!(matches!(result, Err(Error::InvalidRequest(_))));
 
-        // Test invalid email
-        let invalid_event = EventData {
-            event_type: "test_event".into(),
-            email: "invalid-email".into(),
-            fields: None,
-            details: None,
-        };
-        let result = client.track_events(vec![invalid_event]).await;
+        // Test invalid email is rejected when the event is built
+        let result = "invalid-email".parse::<crate::EmailAddress>();
         assert!(matches!(result, Err(Error::InvalidEmail(_))));
 
+        let result = serde_json::from_value::<EventData>(serde_json::json!({
+            "type": "test_event",
+            "email": "a@"
+        }));
+        assert!(result.is_err());
+
         // Test empty event type
         let invalid_event = EventData {
             event_type: "".into(),
-            email: "test@example.com".into(),
+            email: "test@example.com".parse().unwrap(),
             fields: None,
             details: None,
         };
@@ -194,12 +176,14 @@ mod tests {
 
         let event = EventData {
             event_type: "test_event".into(),
-            email: "test@example.com".into(),
+            email: "test@example.com".parse().unwrap(),
             fields: None,
             details: None,
         };
 
-        let result = client.track_events(vec![event]).await;
-        assert!(matches!(result, Err(Error::UnexpectedResponse(_))));
+        let outcome = client.track_events(vec![event]).await.unwrap();
+        assert_eq!(outcome.succeeded, 1);
+        assert_eq!(outcome.failed, 1);
+        assert!(outcome.errors.is_empty());
     }
 }
\ No newline at end of file
@@ -0,0 +1,460 @@
+//! Background buffering for event tracking
+//!
+//! [`EventBuffer`] accepts single events without waiting on the network and sends
+//! them to `/batch/events` in batches, either when enough events have accumulated
+//! or when the flush interval elapses.
+
+use crate::{BatchOutcome, Client, Error, EventData, Result};
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, error};
+
+/// Maximum number of events accepted by `/batch/events` in one request
+const MAX_BATCH_SIZE: usize = 1000;
+
+/// Callback invoked with the error and the events of a batch that failed to send
+pub type ErrorHandler = Arc<dyn Fn(Error, Vec<EventData>) + Send + Sync>;
+
+enum Message {
+    Event(EventData),
+    Flush(oneshot::Sender<()>),
+    Close,
+}
+
+/// Handle to a background task that batches events for [`Client::track_events`]
+///
+/// Dropping the handle without calling [`EventBuffer::close`] still flushes pending
+/// events, but nothing waits for that flush to complete.
+///
+/// # Example
+/// ```no_run
+/// # async fn example(client: bento::Client, event: bento::EventData) -> bento::Result<()> {
+/// use bento::event_buffer::EventBuffer;
+/// use std::time::Duration;
+///
+/// let buffer = EventBuffer::builder(client)
+///     .max_batch_size(500)
+///     .flush_interval(Duration::from_secs(2))
+///     .on_error(|err, events| eprintln!("dropped {} events: {}", events.len(), err))
+///     .build()?;
+///
+/// buffer.push(event)?;
+///
+/// // On shutdown
+/// buffer.close().await;
+/// # Ok(())
+/// # }
+/// ```
+pub struct EventBuffer {
+    sender: mpsc::Sender<Message>,
+    task: JoinHandle<()>,
+}
+
+impl fmt::Debug for EventBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventBuffer").finish_non_exhaustive()
+    }
+}
+
+impl EventBuffer {
+    /// Create a builder for an event buffer that sends through `client`
+    pub fn builder(client: Client) -> EventBufferBuilder {
+        EventBufferBuilder {
+            client,
+            max_batch_size: 100,
+            flush_interval: Duration::from_secs(5),
+            capacity: 10_000,
+            on_error: None,
+        }
+    }
+
+    /// Queue an event without waiting
+    ///
+    /// # Errors
+    /// * `Error::BufferFull` if the queue is at capacity
+    /// * `Error::BufferClosed` if the buffer has been closed
+    pub fn push(&self, event: EventData) -> Result<()> {
+        self.sender.try_send(Message::Event(event)).map_err(|e| match e {
+            mpsc::error::TrySendError::Full(_) => Error::BufferFull,
+            mpsc::error::TrySendError::Closed(_) => Error::BufferClosed,
+        })
+    }
+
+    /// Send all queued events now and wait for the send to finish
+    ///
+    /// # Errors
+    /// Returns `Error::BufferClosed` if the buffer has been closed. Failures to send
+    /// events are reported to the error callback, not returned here.
+    pub async fn flush(&self) -> Result<()> {
+        let (done, wait) = oneshot::channel();
+        self.sender.send(Message::Flush(done)).await.map_err(|_| Error::BufferClosed)?;
+        wait.await.map_err(|_| Error::BufferClosed)
+    }
+
+    /// Flush all queued events and stop the background task
+    pub async fn close(self) {
+        // If the task already stopped there is nothing left to flush
+        let _ = self.sender.send(Message::Close).await;
+        if let Err(e) = self.task.await {
+            error!(error = %e, "event buffer task failed");
+        }
+    }
+}
+
+/// Builder for [`EventBuffer`]
+pub struct EventBufferBuilder {
+    client: Client,
+    max_batch_size: usize,
+    flush_interval: Duration,
+    capacity: usize,
+    on_error: Option<ErrorHandler>,
+}
+
+impl fmt::Debug for EventBufferBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventBufferBuilder")
+            .field("max_batch_size", &self.max_batch_size)
+            .field("flush_interval", &self.flush_interval)
+            .field("capacity", &self.capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EventBufferBuilder {
+    /// Set the number of queued events that triggers a flush (default 100, at most 1000)
+    pub fn max_batch_size(mut self, size: usize) -> Self {
+        self.max_batch_size = size;
+        self
+    }
+
+    /// Set the longest time an event waits before being sent (default 5s)
+    pub fn flush_interval(mut self, interval: Duration) -> Self {
+        self.flush_interval = interval;
+        self
+    }
+
+    /// Set the number of events that can be queued before `push` fails (default 10,000)
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Set the callback invoked when a batch fails to send
+    ///
+    /// Also invoked with `Error::PartialFailure`, carrying the API's [`BatchOutcome`],
+    /// when the API rejects some of the events in a batch. In that case only the
+    /// rejected events are passed, matched by the index or email in the outcome's
+    /// per-item errors; events the API does not identify are not passed, so the list
+    /// may be shorter than the failure count.
+    /// Defaults to logging the failure.
+    pub fn on_error<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(Error, Vec<EventData>) + Send + Sync + 'static,
+    {
+        self.on_error = Some(Arc::new(handler));
+        self
+    }
+
+    /// Start the background task
+    ///
+    /// Must be called from within a tokio runtime.
+    ///
+    /// # Errors
+    /// * `Error::InvalidBatchSize` if the batch size is 0 or above 1000
+    /// * `Error::InvalidConfig` if the capacity or flush interval is zero
+    pub fn build(self) -> Result<EventBuffer> {
+        if self.max_batch_size == 0 || self.max_batch_size > MAX_BATCH_SIZE {
+            return Err(Error::InvalidBatchSize(
+                format!("Batch size must be between 1 and {}", MAX_BATCH_SIZE)
+            ));
+        }
+        if self.capacity == 0 {
+            return Err(Error::InvalidConfig("event buffer capacity must be positive".into()));
+        }
+        if self.flush_interval.is_zero() {
+            return Err(Error::InvalidConfig("flush interval must be positive".into()));
+        }
+
+        let (sender, receiver) = mpsc::channel(self.capacity);
+        let on_error = self.on_error.unwrap_or_else(|| {
+            Arc::new(|err, events: Vec<EventData>| {
+                error!(error = %err, count = events.len(), "failed to flush buffered events");
+            })
+        });
+
+        let worker = Worker {
+            client: self.client,
+            max_batch_size: self.max_batch_size,
+            on_error,
+            pending: Vec::with_capacity(self.max_batch_size),
+        };
+        let task = tokio::spawn(worker.run(receiver, self.flush_interval));
+
+        Ok(EventBuffer { sender, task })
+    }
+}
+
+struct Worker {
+    client: Client,
+    max_batch_size: usize,
+    on_error: ErrorHandler,
+    pending: Vec<EventData>,
+}
+
+impl Worker {
+    async fn run(mut self, mut receiver: mpsc::Receiver<Message>, flush_interval: Duration) {
+        let mut interval = tokio::time::interval(flush_interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        interval.tick().await;
+
+        loop {
+            tokio::select! {
+                message = receiver.recv() => match message {
+                    Some(Message::Event(event)) => {
+                        self.pending.push(event);
+                        if self.pending.len() >= self.max_batch_size {
+                            self.flush().await;
+                        }
+                    }
+                    Some(Message::Flush(done)) => {
+                        self.flush().await;
+                        let _ = done.send(());
+                    }
+                    Some(Message::Close) | None => break,
+                },
+                _ = interval.tick() => self.flush().await,
+            }
+        }
+
+        // Drain anything queued before the buffer was closed
+        receiver.close();
+        while let Ok(message) = receiver.try_recv() {
+            match message {
+                Message::Event(event) => self.pending.push(event),
+                Message::Flush(done) => {
+                    let _ = done.send(());
+                }
+                Message::Close => {}
+            }
+        }
+        self.flush().await;
+    }
+
+    async fn flush(&mut self) {
+        while !self.pending.is_empty() {
+            let count = self.pending.len().min(self.max_batch_size);
+            let batch: Vec<EventData> = self.pending.drain(..count).collect();
+
+            debug!(count, "flushing buffered events");
+            match self.client.track_events(batch.clone()).await {
+                Ok(outcome) if !outcome.is_complete_success() => {
+                    let rejected = rejected_events(batch, &outcome);
+                    (self.on_error)(Error::PartialFailure(outcome), rejected);
+                }
+                Ok(_) => {}
+                Err(err) => (self.on_error)(err, batch),
+            }
+        }
+    }
+}
+
+/// Picks the events named by the outcome's per-item errors, by index or else by email.
+fn rejected_events(batch: Vec<EventData>, outcome: &BatchOutcome) -> Vec<EventData> {
+    let indexes: HashSet<usize> = outcome.errors.iter().filter_map(|e| e.index).collect();
+    let emails: HashSet<String> = outcome.errors.iter()
+        .filter(|e| e.index.is_none())
+        .filter_map(|e| e.email.as_deref().map(str::to_lowercase))
+        .collect();
+
+    batch.into_iter()
+        .enumerate()
+        .filter(|(index, event)| {
+            indexes.contains(index) || emails.contains(&event.email.as_str().to_lowercase())
+        })
+        .map(|(_, event)| event)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+    use wiremock::matchers::{method, path};
+
+    fn event(n: usize) -> EventData {
+        EventData {
+            event_type: "test_event".into(),
+            email: format!("user{}@example.com", n).parse().unwrap(),
+            fields: None,
+            details: None,
+        }
+    }
+
+    async fn received_batch_sizes(mock_server: &MockServer) -> Vec<usize> {
+        mock_server.received_requests().await.unwrap()
+            .iter()
+            .map(|request| {
+                let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+                body["events"].as_array().unwrap().len()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_flushes_on_batch_size_and_close() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/batch/events"))
+            .respond_with(ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({
+                    "results": 1,
+                    "failed": 0
+                })))
+            .mount(&mock_server)
+            .await;
+
+        let client = crate::test_utils::create_test_client(mock_server.uri());
+        let buffer = EventBuffer::builder(client)
+            .max_batch_size(2)
+            .flush_interval(Duration::from_secs(3600))
+            .build()
+            .unwrap();
+
+        for n in 0..3 {
+            buffer.push(event(n)).unwrap();
+        }
+        buffer.flush().await.unwrap();
+        assert_eq!(received_batch_sizes(&mock_server).await, vec![2, 1]);
+
+        buffer.push(event(3)).unwrap();
+        buffer.close().await;
+        assert_eq!(received_batch_sizes(&mock_server).await, vec![2, 1, 1]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_flushes_on_interval() {
+        let bento = crate::testing::FakeBento::new();
+        let buffer = EventBuffer::builder(bento.client())
+            .flush_interval(Duration::from_secs(5))
+            .build()
+            .unwrap();
+
+        buffer.push(event(0)).unwrap();
+        tokio::time::sleep(Duration::from_secs(4)).await;
+        assert!(bento.events().is_empty());
+
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        assert_eq!(bento.events().len(), 1);
+        assert_eq!(bento.requests().len(), 1);
+        buffer.close().await;
+    }
+
+    #[tokio::test]
+    async fn test_reports_failures_to_callback() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/batch/events"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&mock_server)
+            .await;
+
+        let failed = Arc::new(Mutex::new(Vec::new()));
+        let failed_events = Arc::clone(&failed);
+
+        let client = crate::test_utils::create_test_client(mock_server.uri());
+        let buffer = EventBuffer::builder(client)
+            .on_error(move |err, events| {
+                assert_eq!(err.status(), Some(500));
+                failed_events.lock().unwrap().extend(events);
+            })
+            .build()
+            .unwrap();
+
+        buffer.push(event(0)).unwrap();
+        buffer.push(event(1)).unwrap();
+        buffer.close().await;
+
+        assert_eq!(failed.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_reports_only_rejected_events() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/batch/events"))
+            .respond_with(ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({
+                    "results": 2,
+                    "failed": 2,
+                    "errors": [
+                        { "index": 1, "message": "invalid event" },
+                        { "email": "USER3@example.com", "message": "unsubscribed" }
+                    ]
+                })))
+            .mount(&mock_server)
+            .await;
+
+        let failed = Arc::new(Mutex::new(Vec::new()));
+        let failed_events = Arc::clone(&failed);
+
+        let client = crate::test_utils::create_test_client(mock_server.uri());
+        let buffer = EventBuffer::builder(client)
+            .on_error(move |err, events| {
+                match err {
+                    Error::PartialFailure(outcome) => {
+                        assert_eq!((outcome.succeeded, outcome.failed), (2, 2));
+                        assert_eq!(outcome.errors[1].message.as_deref(), Some("unsubscribed"));
+                    }
+                    other => panic!("unexpected error: {:?}", other),
+                }
+                failed_events.lock().unwrap().extend(events);
+            })
+            .build()
+            .unwrap();
+
+        for n in 0..4 {
+            buffer.push(event(n)).unwrap();
+        }
+        buffer.close().await;
+
+        let emails: Vec<String> = failed.lock().unwrap().iter().map(|e| e.email.to_string()).collect();
+        assert_eq!(emails, vec!["user1@example.com", "user3@example.com"]);
+    }
+
+    #[tokio::test]
+    async fn test_push_when_full_or_closed() {
+        let (sender, receiver) = mpsc::channel(1);
+        let buffer = EventBuffer { sender, task: tokio::spawn(async {}) };
+
+        buffer.push(event(0)).unwrap();
+        assert!(matches!(buffer.push(event(1)), Err(Error::BufferFull)));
+
+        drop(receiver);
+        assert!(matches!(buffer.push(event(2)), Err(Error::BufferClosed)));
+        assert!(matches!(buffer.flush().await, Err(Error::BufferClosed)));
+    }
+
+    #[tokio::test]
+    async fn test_builder_validation() {
+        let mock_server = MockServer::start().await;
+        let client = crate::test_utils::create_test_client(mock_server.uri());
+
+        let result = EventBuffer::builder(client.clone()).max_batch_size(0).build();
+        assert!(matches!(result, Err(Error::InvalidBatchSize(_))));
+
+        let result = EventBuffer::builder(client.clone()).max_batch_size(1001).build();
+        assert!(matches!(result, Err(Error::InvalidBatchSize(_))));
+
+        let result = EventBuffer::builder(client).flush_interval(Duration::ZERO).build();
+        assert!(matches!(result, Err(Error::InvalidConfig(_))));
+    }
+}
\ No newline at end of file
@@ -1,5 +1,7 @@
-use crate::{Client, Error, Result};
+use crate::{Client, EmailAddress, Error, Result};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
 use std::net::IpAddr;
 use tracing::instrument;
 
@@ -31,16 +33,131 @@ pub struct ValidationData {
 }
 
 /// Response from email validation
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ValidationResponse {
     /// Whether the email is valid
     pub valid: bool,
+    /// Why the email was judged invalid, empty when valid
+    #[serde(default)]
+    pub reasons: Vec<String>,
+}
+
+/// Blacklist status of a domain or IP address
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlacklistStatus {
+    /// Domain or IP address that was checked
+    #[serde(default)]
+    pub query: Option<String>,
+    /// Summary of the result, e.g. `Not listed`
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Listing result per RBL; `true` means listed
+    #[serde(default)]
+    pub results: HashMap<String, bool>,
+    /// Keys not covered by the fields above
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl BlacklistStatus {
+    /// Returns whether any RBL lists the query
+    pub fn is_listed(&self) -> bool {
+        self.results.values().any(|listed| *listed)
+    }
+
+    /// Returns the RBLs that list the query, sorted by name
+    pub fn listed_on(&self) -> Vec<&str> {
+        let mut listed: Vec<&str> = self.results.iter()
+            .filter(|(_, listed)| **listed)
+            .map(|(rbl, _)| rbl.as_str())
+            .collect();
+        listed.sort_unstable();
+        listed
+    }
+}
+
+/// Result of content moderation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContentModeration {
+    /// Whether the content was flagged in any category
+    #[serde(default)]
+    pub flagged: bool,
+    /// Whether the content was flagged, per category
+    #[serde(default)]
+    pub categories: HashMap<String, bool>,
+    /// Confidence score between 0 and 1, per category
+    #[serde(default)]
+    pub category_scores: HashMap<String, f64>,
+    /// Keys not covered by the fields above
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// Predicted gender
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Gender {
+    /// Predicted male
+    Male,
+    /// Predicted female
+    Female,
+    /// No prediction could be made
+    #[serde(other)]
+    Unknown,
+}
+
+/// Gender prediction for a name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenderPrediction {
+    /// Predicted gender
+    pub gender: Gender,
+    /// Confidence of the prediction between 0 and 1, if the API reported one
+    #[serde(default)]
+    pub confidence: Option<f64>,
+    /// Keys not covered by the fields above
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// Location of an IP address
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Geolocation {
+    /// IP address that was located
+    #[serde(default)]
+    pub ip: Option<String>,
+    /// Country name, e.g. `Australia`
+    #[serde(default)]
+    pub country_name: Option<String>,
+    /// Two-letter country code, e.g. `AU`
+    #[serde(default, rename = "country_code2")]
+    pub country_code: Option<String>,
+    /// Region or state name
+    #[serde(default)]
+    pub region_name: Option<String>,
+    /// City name
+    #[serde(default)]
+    pub city_name: Option<String>,
+    /// Postal code
+    #[serde(default)]
+    pub postal_code: Option<String>,
+    /// Latitude in degrees
+    #[serde(default)]
+    pub latitude: Option<f64>,
+    /// Longitude in degrees
+    #[serde(default)]
+    pub longitude: Option<f64>,
+    /// IANA timezone, e.g. `Australia/Sydney`
+    #[serde(default)]
+    pub timezone: Option<String>,
+    /// Keys not covered by the fields above
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
 impl Client {
     /// Check domain or IP address blacklist status
-    #[instrument(skip(self))]
-    pub async fn get_blacklist_status(&self, data: &BlacklistData) -> Result<serde_json::Value> {
+    #[instrument(skip_all)]
+    pub async fn get_blacklist_status(&self, data: &BlacklistData) -> Result<BlacklistStatus> {
         if data.domain.is_none() && data.ip.is_none() {
             return Err(Error::InvalidRequest("Either domain or IP is required".into()));
         }
@@ -52,7 +169,7 @@ impl Client {
         }
 
         let url = self.build_url("/experimental/blacklist.json")?;
-        let mut request = self.http_client.get(&url);
+        let mut request = self.get(&url);
 
         if let Some(domain) = &data.domain {
             request = request.query(&[("domain", domain)]);
@@ -62,16 +179,14 @@ impl Client {
         }
 
         let response = self.request(request).await?;
-        let result = response.json().await?;
+        let result = response.json()?;
         Ok(result)
     }
 
     /// Validate email address with additional context
-    #[instrument(skip(self))]
+    #[instrument(skip_all, fields(email = %self.redact(&data.email)))]
     pub async fn validate_email(&self, data: &ValidationData) -> Result<ValidationResponse> {
-        if !data.email.contains('@') {
-            return Err(Error::InvalidEmail(data.email.clone()));
-        }
+        EmailAddress::parse(&data.email)?;
 
         if let Some(ip) = &data.ip {
             if ip.parse::<IpAddr>().is_err() {
@@ -81,66 +196,62 @@ impl Client {
 
         let url = self.build_url("/experimental/validation")?;
         let response = self.request(
-            self.http_client
-                .post(&url)
+            self.post(&url)
                 .json(data)
         ).await?;
 
-        let result = response.json().await?;
+        let result = response.json()?;
         Ok(result)
     }
 
     /// Moderate content
-    #[instrument(skip(self))]
-    pub async fn get_content_moderation(&self, content: &str) -> Result<serde_json::Value> {
+    #[instrument(skip_all)]
+    pub async fn get_content_moderation(&self, content: &str) -> Result<ContentModeration> {
         if content.is_empty() {
             return Err(Error::InvalidContent("Content is required".into()));
         }
 
         let url = self.build_url("/experimental/content_moderation")?;
         let response = self.request(
-            self.http_client
-                .post(&url)
+            self.post(&url)
                 .query(&[("content", content)])
         ).await?;
 
-        let result = response.json().await?;
+        let result = response.json()?;
         Ok(result)
     }
 
     /// Predict gender from name
-    #[instrument(skip(self))]
-    pub async fn get_gender(&self, name: &str) -> Result<serde_json::Value> {
+    #[instrument(skip_all)]
+    pub async fn get_gender(&self, name: &str) -> Result<GenderPrediction> {
         if name.is_empty() {
             return Err(Error::InvalidName("Name is required".into()));
         }
 
         let url = self.build_url("/experimental/gender")?;
         let response = self.request(
-            self.http_client
-                .post(&url)
+            self.post(&url)
                 .query(&[("name", name)])
         ).await?;
 
-        let result = response.json().await?;
+        let result = response.json()?;
         Ok(result)
     }
 
     /// Geolocate IP address
-    #[instrument(skip(self))]
-    pub async fn geolocate_ip(&self, ip: &str) -> Result<serde_json::Value> {
+    #[instrument(skip_all)]
+    pub async fn geolocate_ip(&self, ip: &str) -> Result<Geolocation> {
         if ip.parse::<IpAddr>().is_err() {
             return Err(Error::InvalidIpAddress(ip.to_string()));
         }
 
         let url = self.build_url("/experimental/geolocation")?;
         let response = self.request(
-            self.http_client
-                .get(&url)
+            self.get(&url)
                 .query(&[("ip", ip)])
         ).await?;
 
-        let result = response.json().await?;
+        let result = response.json()?;
         Ok(result)
     }
 }
@@ -160,19 +271,131 @@ mod tests {
             .and(query_param("domain", "example.com"))
             .respond_with(ResponseTemplate::new(200)
                 .set_body_json(serde_json::json!({
-                    "status": "clean"
+                    "query": "example.com",
+                    "description": "Listed on 1 blacklist",
+                    "results": { "spamhaus": true, "barracuda": false, "spamcop": false }
                 })))
             .mount(&mock_server)
             .await;
 
         let client = crate::test_utils::create_test_client(mock_server.uri());
-        let result = client.get_blacklist_status(&BlacklistData {
+        let status = client.get_blacklist_status(&BlacklistData {
             domain: Some("example.com".into()),
             ip: None,
-        }).await;
+        }).await.unwrap();
+
+        assert_eq!(status.query.as_deref(), Some("example.com"));
+        assert!(status.is_listed());
+        assert_eq!(status.listed_on(), vec!["spamhaus"]);
+    }
+
+    #[tokio::test]
+    async fn test_validate_email_reasons() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/experimental/validation"))
+            .respond_with(ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({
+                    "valid": false,
+                    "reasons": ["disposable domain"]
+                })))
+            .mount(&mock_server)
+            .await;
+
+        let client = crate::test_utils::create_test_client(mock_server.uri());
+        let response = client.validate_email(&ValidationData {
+            email: "test@mailinator.com".into(),
+            name: None,
+            user_agent: None,
+            ip: None,
+        }).await.unwrap();
 
-        assert!(result.is_ok());
+        assert!(!response.valid);
+        assert_eq!(response.reasons, vec!["disposable domain"]);
     }
 
-    // Additional tests...
-}
\ No newline at end of file
+    #[tokio::test]
+    async fn test_content_moderation() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/experimental/content_moderation"))
+            .respond_with(ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({
+                    "flagged": true,
+                    "categories": { "harassment": true, "violence": false },
+                    "category_scores": { "harassment": 0.91, "violence": 0.02 }
+                })))
+            .mount(&mock_server)
+            .await;
+
+        let client = crate::test_utils::create_test_client(mock_server.uri());
+        let moderation = client.get_content_moderation("Some content").await.unwrap();
+
+        assert!(moderation.flagged);
+        assert!(moderation.categories["harassment"]);
+        assert_eq!(moderation.category_scores["violence"], 0.02);
+    }
+
+    #[tokio::test]
+    async fn test_gender_prediction() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/experimental/gender"))
+            .respond_with(ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "gender": "female", "confidence": 0.97 })))
+            .mount(&mock_server)
+            .await;
+
+        let client = crate::test_utils::create_test_client(mock_server.uri());
+        let prediction = client.get_gender("Jane").await.unwrap();
+
+        assert_eq!(prediction.gender, Gender::Female);
+        assert_eq!(prediction.confidence, Some(0.97));
+
+        let unknown: GenderPrediction = serde_json::from_value(serde_json::json!({
+            "gender": "androgynous", "confidence": 0.5
+        })).unwrap();
+        assert_eq!(unknown.gender, Gender::Unknown);
+        assert_eq!(unknown.confidence, Some(0.5));
+
+        let missing: GenderPrediction = serde_json::from_value(serde_json::json!({
+            "gender": "unknown"
+        })).unwrap();
+        assert_eq!(missing.confidence, None);
+    }
+
+    #[tokio::test]
+    async fn test_geolocate_ip() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/experimental/geolocation"))
+            .and(query_param("ip", "1.1.1.1"))
+            .respond_with(ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({
+                    "ip": "1.1.1.1",
+                    "country_name": "Australia",
+                    "country_code2": "AU",
+                    "country_code3": "AUS",
+                    "region_name": "NSW",
+                    "city_name": "Sydney",
+                    "latitude": -33.86,
+                    "longitude": 151.2
+                })))
+            .mount(&mock_server)
+            .await;
+
+        let client = crate::test_utils::create_test_client(mock_server.uri());
+        let location = client.geolocate_ip("1.1.1.1").await.unwrap();
+
+        assert_eq!(location.country_code.as_deref(), Some("AU"));
+        assert_eq!(location.region_name.as_deref(), Some("NSW"));
+        assert_eq!(location.city_name.as_deref(), Some("Sydney"));
+        assert_eq!(location.latitude, Some(-33.86));
+        assert_eq!(location.longitude, Some(151.2));
+        assert_eq!(location.extra["country_code3"], "AUS");
+    }
+}
@@ -40,11 +40,11 @@ impl Client {
     /// # Errors
     ///
     /// Returns an error if the API request fails or if the response cannot be parsed
-    #[instrument(skip(self))]
+    #[instrument(skip_all)]
     pub async fn get_fields(&self) -> Result<Vec<FieldData>> {
         let url = self.build_url("/fetch/fields")?;
         let response = self.request(
-            self.http_client.get(&url)
+            self.get(&url)
         ).await?;
 
         #[derive(Deserialize)]
@@ -52,7 +52,7 @@ impl Client {
             data: Vec<FieldData>,
         }
 
-        let field_response: FieldResponse = response.json().await?;
+        let field_response: FieldResponse = response.json()?;
         Ok(field_response.data)
     }
 
@@ -68,7 +68,7 @@ impl Client {
     /// * The key is empty
     /// * The API request fails
     /// * The response cannot be parsed
-    #[instrument(skip(self))]
+    #[instrument(skip_all, fields(key = %key))]
     pub async fn create_field(&self, key: &str) -> Result<FieldData> {
         if key.is_empty() {
             return Err(Error::InvalidRequest("Field key is required".into()));
@@ -76,8 +76,7 @@ impl Client {
 
         let url = self.build_url("/fetch/fields")?;
         let response = self.request(
-            self.http_client
-                .post(&url)
+            self.post(&url)
                 .json(&serde_json::json!({
                     "field": {
                         "key": key
@@ -90,7 +89,7 @@ impl Client {
             data: FieldData,
         }
 
-        let field_response: FieldResponse = response.json().await?;
+        let field_response: FieldResponse = response.json()?;
         Ok(field_response.data)
     }
 }
@@ -0,0 +1,105 @@
+//! Hooks that run around every request made by the client.
+
+use crate::{Error, HttpRequest, HttpResponse, Result};
+use async_trait::async_trait;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Middleware hook around the requests made by a [`Client`](crate::Client)
+///
+/// Interceptors are registered with
+/// [`ConfigBuilder::interceptor`](crate::ConfigBuilder::interceptor) and run once per
+/// API call, around all retry attempts, in registration order. `on_request` sees the
+/// request before authentication headers are added, so interceptors never observe
+/// the credentials. All methods have no-op defaults.
+///
+/// # Example
+/// ```
+/// use bento::{ConfigBuilder, HttpRequest, Interceptor};
+///
+/// struct CorrelationId;
+///
+/// #[async_trait::async_trait]
+/// impl Interceptor for CorrelationId {
+///     async fn on_request(&self, request: &mut HttpRequest) -> bento::Result<()> {
+///         request.headers.push(("X-Correlation-Id".into(), "req-123".into()));
+///         Ok(())
+///     }
+/// }
+///
+/// let config = ConfigBuilder::new()
+///     .publishable_key("pub_key")
+///     .secret_key("secret_key")
+///     .site_uuid("site_uuid")
+///     .interceptor(CorrelationId)
+///     .build();
+/// ```
+#[async_trait]
+pub trait Interceptor: Send + Sync {
+    /// Inspect or modify a request before it is sent
+    ///
+    /// # Errors
+    /// Returning an error aborts the call with that error: later interceptors are
+    /// skipped, nothing is sent, and the interceptors that already ran get
+    /// [`on_error`](Self::on_error) with that error.
+    async fn on_request(&self, _request: &mut HttpRequest) -> Result<()> {
+        Ok(())
+    }
+
+    /// Observe the final response of a call, including non-2xx statuses
+    ///
+    /// `elapsed` covers every attempt, including retry delays.
+    async fn on_response(&self, _request: &HttpRequest, _response: &HttpResponse, _elapsed: Duration) {}
+
+    /// Observe a call that failed without a response, after retries are exhausted
+    ///
+    /// Also called when a later interceptor aborts the call in `on_request`, so
+    /// every successful `on_request` is paired with `on_response` or `on_error`.
+    async fn on_error(&self, _request: &HttpRequest, _error: &Error, _elapsed: Duration) {}
+}
+
+/// Interceptors registered on a configuration, in registration order
+#[derive(Clone, Default)]
+pub(crate) struct InterceptorChain {
+    interceptors: Vec<Arc<dyn Interceptor>>,
+}
+
+impl fmt::Debug for InterceptorChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InterceptorChain")
+            .field("len", &self.interceptors.len())
+            .finish()
+    }
+}
+
+impl InterceptorChain {
+    pub(crate) fn push(&mut self, interceptor: Arc<dyn Interceptor>) {
+        self.interceptors.push(interceptor);
+    }
+
+    pub(crate) async fn on_request(&self, request: &mut HttpRequest) -> Result<()> {
+        let started = Instant::now();
+        for (ran, interceptor) in self.interceptors.iter().enumerate() {
+            if let Err(err) = interceptor.on_request(request).await {
+                for earlier in &self.interceptors[..ran] {
+                    earlier.on_error(request, &err, started.elapsed()).await;
+                }
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn on_response(&self, request: &HttpRequest, response: &HttpResponse, elapsed: Duration) {
+        for interceptor in &self.interceptors {
+            interceptor.on_response(request, response, elapsed).await;
+        }
+    }
+
+    pub(crate) async fn on_error(&self, request: &HttpRequest, error: &Error, elapsed: Duration) {
+        for interceptor in &self.interceptors {
+            interceptor.on_error(request, error, elapsed).await;
+        }
+    }
+}
\ No newline at end of file
@@ -12,7 +12,13 @@ pub type Result<T> = std::result::Result<T, Error>;
 
 mod client;
 mod config;
+mod email_address;
 mod error;
+mod interceptor;
+mod rate_limit;
+mod redaction;
+mod retry;
+mod transport;
 mod types;
 
 /// The broadcast module provides functionality for managing and interacting with broadcasts.
@@ -24,9 +30,16 @@ pub mod email;
 /// The event module contains tools for managing events and event data.
 pub mod event;
 
+/// The event_buffer module provides background batching of tracked events.
+pub mod event_buffer;
+
 /// The experimental module includes features that are in a testing or beta phase.
 pub mod experimental;
 
+/// The testing module provides an in-memory fake of the Bento API for downstream tests.
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
 /// The field module provides structures and utilities for working with fields in Bento.
 pub mod field;
 
@@ -36,18 +49,44 @@ pub mod subscriber;
 /// The tag module provides functionality for working with tags.
 pub mod tag;
 
+/// The deliverability module monitors sending domains and IPs for blacklist changes.
+pub mod deliverability;
+
+/// The validation module provides bulk email validation with local pre-checks and caching.
+pub mod validation;
+
 /// The stats module includes tools for accessing and manipulating statistical data.
 pub mod stats;
 
 /// The commands module provides functionality for executing subscriber commands.
 pub mod commands;
 
+/// The webhook module provides signature verification for webhook deliveries.
+pub mod webhook;
+
+/// The axum module provides a webhook route and an event tracking layer for axum services.
+#[cfg(feature = "axum")]
+pub mod axum;
+
+/// The metrics module names the metrics recorded for every API call.
+#[cfg(feature = "metrics")]
+pub mod metrics;
+
+/// The prometheus module exports site and segment statistics as Prometheus gauges.
+#[cfg(feature = "prometheus")]
+pub mod prometheus;
+
 #[cfg(test)]
 mod test_utils;
 
 pub use client::Client;
 pub use config::{Config, ConfigBuilder};
+pub use email_address::EmailAddress;
 pub use error::Error;
+pub use interceptor::Interceptor;
+pub use redaction::Redaction;
+pub use retry::RetryPolicy;
+pub use transport::{HttpRequest, HttpResponse, Method, ReqwestTransport, Transport};
 pub use types::*;
 
 /// Current version of the SDK
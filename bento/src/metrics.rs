@@ -0,0 +1,140 @@
+//! Metrics recorded for API calls when the `metrics` feature is enabled.
+//!
+//! Metrics are emitted through the [`metrics`](::metrics) facade, so they reach
+//! whichever recorder the application installs (Prometheus, StatsD, ...). Nothing is
+//! recorded until a recorder is installed.
+//!
+//! | Metric | Type | Labels |
+//! |---|---|---|
+//! | [`REQUESTS_TOTAL`] | counter | `method`, `endpoint`, `status` |
+//! | [`REQUEST_DURATION_SECONDS`] | histogram | `method`, `endpoint` |
+//! | [`RETRIES_TOTAL`] | counter | `endpoint` |
+//! | [`RATE_LIMITED_TOTAL`] | counter | `endpoint`, `source` |
+//! | [`BATCH_SIZE`] | histogram | `endpoint` |
+//!
+//! `endpoint` is the request path without its query, e.g. `/batch/events`. `status`
+//! is the final HTTP status of a call, or `error` if no response was received.
+//! `source` is `server` for 429 responses and `client` when the client-side rate
+//! limit delayed a request.
+
+use crate::Method;
+use std::time::Duration;
+
+/// Number of API calls, counted once per call after retries
+pub const REQUESTS_TOTAL: &str = "bento_requests_total";
+
+/// Duration of API calls in seconds, including retries and their delays
+pub const REQUEST_DURATION_SECONDS: &str = "bento_request_duration_seconds";
+
+/// Number of retry attempts
+pub const RETRIES_TOTAL: &str = "bento_retries_total";
+
+/// Number of times a request was rate limited
+pub const RATE_LIMITED_TOTAL: &str = "bento_rate_limited_total";
+
+/// Number of items sent in each batch request
+pub const BATCH_SIZE: &str = "bento_batch_size";
+
+pub(crate) fn record_request(method: Method, endpoint: &str, status: Option<u16>, elapsed: Duration) {
+    let status = status.map_or_else(|| "error".to_string(), |status| status.to_string());
+    ::metrics::counter!(
+        REQUESTS_TOTAL,
+        "method" => method.as_str(),
+        "endpoint" => endpoint.to_string(),
+        "status" => status
+    )
+    .increment(1);
+    ::metrics::histogram!(
+        REQUEST_DURATION_SECONDS,
+        "method" => method.as_str(),
+        "endpoint" => endpoint.to_string()
+    )
+    .record(elapsed.as_secs_f64());
+}
+
+pub(crate) fn record_retry(endpoint: &str) {
+    ::metrics::counter!(RETRIES_TOTAL, "endpoint" => endpoint.to_string()).increment(1);
+}
+
+pub(crate) fn record_rate_limited(endpoint: &str, source: &'static str) {
+    ::metrics::counter!(RATE_LIMITED_TOTAL, "endpoint" => endpoint.to_string(), "source" => source).increment(1);
+}
+
+pub(crate) fn record_batch(endpoint: &'static str, items: usize) {
+    ::metrics::histogram!(BATCH_SIZE, "endpoint" => endpoint).record(items as f64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{FakeBento, FAKE_BASE_URL};
+    use crate::{Client, Command, ConfigBuilder, RetryPolicy};
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+    use std::collections::HashMap;
+
+    /// Metric values keyed by name and sorted `key=value` labels
+    type Recorded = HashMap<(String, Vec<String>), DebugValue>;
+
+    fn value<'a>(recorded: &'a Recorded, name: &str, labels: &[&str]) -> Option<&'a DebugValue> {
+        recorded.get(&(name.to_string(), labels.iter().map(|l| l.to_string()).collect()))
+    }
+
+    #[test]
+    fn test_records_request_metrics() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let fake = FakeBento::new();
+        let config = ConfigBuilder::new()
+            .publishable_key("pub_key")
+            .secret_key("secret_key")
+            .site_uuid("site_123")
+            .base_url(FAKE_BASE_URL)
+            .retry_policy(RetryPolicy::new().max_retries(1).base_delay(Duration::from_millis(1)))
+            .build()
+            .unwrap();
+        let client = Client::with_transport(config, fake.clone());
+
+        ::metrics::with_local_recorder(&recorder, || {
+            let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+            runtime.block_on(async {
+                let commands = vec![
+                    Command::add_tag("test@example.com".parse().unwrap(), "lead"),
+                    Command::subscribe("test@example.com".parse().unwrap()),
+                ];
+                fake.fail_next(429);
+                client.subscriber_command(commands).await.unwrap();
+
+                fake.fail_next(500);
+                assert!(client.get_tags().await.is_err());
+            });
+        });
+
+        let recorded: Recorded = snapshotter.snapshot()
+            .into_vec()
+            .into_iter()
+            .map(|(key, _, _, value)| {
+                let (_, key) = key.into_parts();
+                let mut labels: Vec<String> = key.labels().map(|l| format!("{}={}", l.key(), l.value())).collect();
+                labels.sort();
+                ((key.name().to_string(), labels), value)
+            })
+            .collect();
+
+        let commands = ["endpoint=/fetch/commands", "method=POST"];
+        assert_eq!(value(&recorded, REQUESTS_TOTAL, &[commands[0], commands[1], "status=200"]), Some(&DebugValue::Counter(1)));
+        assert_eq!(value(&recorded, RETRIES_TOTAL, &["endpoint=/fetch/commands"]), Some(&DebugValue::Counter(1)));
+        assert_eq!(
+            value(&recorded, RATE_LIMITED_TOTAL, &["endpoint=/fetch/commands", "source=server"]),
+            Some(&DebugValue::Counter(1))
+        );
+        assert!(matches!(
+            value(&recorded, BATCH_SIZE, &["endpoint=/fetch/commands"]),
+            Some(DebugValue::Histogram(sizes)) if sizes.len() == 1 && sizes[0] == 2.0
+        ));
+
+        let tags = ["endpoint=/fetch/tags", "method=GET"];
+        assert_eq!(value(&recorded, REQUESTS_TOTAL, &[tags[0], tags[1], "status=500"]), Some(&DebugValue::Counter(1)));
+        assert!(matches!(value(&recorded, REQUEST_DURATION_SECONDS, &tags), Some(DebugValue::Histogram(d)) if d.len() == 1));
+        assert_eq!(value(&recorded, RETRIES_TOTAL, &["endpoint=/fetch/tags"]), None);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,429 @@
+//! Prometheus exporter for site and segment statistics
+//!
+//! [`StatsExporter`] polls [`Client::get_site_stats`] and
+//! [`Client::get_segment_stats`] on an interval and serves the latest values as
+//! Prometheus gauges on `/metrics`:
+//!
+//! | Metric | Labels |
+//! |---|---|
+//! | [`SITE_USERS`], [`SITE_SUBSCRIBERS`], [`SITE_UNSUBSCRIBERS`] | |
+//! | [`SEGMENT_USERS`], [`SEGMENT_SUBSCRIBERS`], [`SEGMENT_UNSUBSCRIBERS`] | `segment_id` |
+//! | [`UP`] | `target`, `segment_id` |
+//! | [`LAST_POLL_TIMESTAMP_SECONDS`] | |
+//!
+//! `target` is `site` or `segment`. [`UP`] is 1 when the latest poll of a target
+//! succeeded and 0 when it failed; the gauges for a failed target keep their last
+//! known values.
+//!
+//! Requires the `prometheus` feature.
+
+use crate::stats::{SegmentStats, SiteStats};
+use crate::{Client, Error, Result};
+use ::axum::http::header::CONTENT_TYPE;
+use ::axum::routing::get;
+use ::axum::Router;
+use futures::future::join_all;
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::warn;
+
+/// Number of people known to the site
+pub const SITE_USERS: &str = "bento_site_users";
+
+/// Number of active subscribers on the site
+pub const SITE_SUBSCRIBERS: &str = "bento_site_subscribers";
+
+/// Number of unsubscribed people on the site
+pub const SITE_UNSUBSCRIBERS: &str = "bento_site_unsubscribers";
+
+/// Number of people in a segment
+pub const SEGMENT_USERS: &str = "bento_segment_users";
+
+/// Number of active subscribers in a segment
+pub const SEGMENT_SUBSCRIBERS: &str = "bento_segment_subscribers";
+
+/// Number of unsubscribed people in a segment
+pub const SEGMENT_UNSUBSCRIBERS: &str = "bento_segment_unsubscribers";
+
+/// Whether the latest poll of a target succeeded
+pub const UP: &str = "bento_stats_up";
+
+/// Unix time of the latest poll
+pub const LAST_POLL_TIMESTAMP_SECONDS: &str = "bento_stats_last_poll_timestamp_seconds";
+
+/// Content type of the Prometheus text exposition format
+const TEXT_FORMAT: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Latest result for one target
+#[derive(Debug)]
+struct Target<T> {
+    stats: Option<T>,
+    up: bool,
+}
+
+impl<T> Default for Target<T> {
+    fn default() -> Self {
+        Self { stats: None, up: false }
+    }
+}
+
+impl<T> Target<T> {
+    fn update(&mut self, result: Result<T>, target: &str) {
+        match result {
+            Ok(stats) => {
+                self.stats = Some(stats);
+                self.up = true;
+            }
+            Err(err) => {
+                warn!(error = %err, target, "failed to poll Bento stats");
+                self.up = false;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Snapshot {
+    site: Target<SiteStats>,
+    segments: BTreeMap<String, Target<SegmentStats>>,
+    last_poll: Option<SystemTime>,
+}
+
+/// Polls Bento statistics and renders them as Prometheus gauges
+///
+/// Cloning an exporter returns another handle to the same values, so one clone can
+/// poll while another serves `/metrics`.
+///
+/// # Example
+/// ```no_run
+/// # async fn example(client: bento::Client) -> Result<(), Box<dyn std::error::Error>> {
+/// use bento::prometheus::StatsExporter;
+/// use std::time::Duration;
+/// use tokio::net::TcpListener;
+///
+/// let exporter = StatsExporter::builder(client)
+///     .segment("seg_123")
+///     .interval(Duration::from_secs(60))
+///     .build()?;
+///
+/// let listener = TcpListener::bind("127.0.0.1:9464").await?;
+/// exporter.serve(listener).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct StatsExporter {
+    client: Client,
+    segments: Arc<[String]>,
+    interval: Duration,
+    snapshot: Arc<RwLock<Snapshot>>,
+}
+
+impl fmt::Debug for StatsExporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StatsExporter")
+            .field("segments", &self.segments)
+            .field("interval", &self.interval)
+            .finish_non_exhaustive()
+    }
+}
+
+impl StatsExporter {
+    /// Create a builder for an exporter that polls through `client`
+    pub fn builder(client: Client) -> StatsExporterBuilder {
+        StatsExporterBuilder {
+            client,
+            segments: Vec::new(),
+            interval: Duration::from_secs(60),
+        }
+    }
+
+    /// Poll the site and every segment once and store the results
+    ///
+    /// Failures are logged and reported through the [`UP`] gauge rather than returned.
+    pub async fn poll(&self) {
+        let site = self.client.get_site_stats();
+        let segments = join_all(self.segments.iter().map(|id| self.client.get_segment_stats(id)));
+        let (site, segments) = tokio::join!(site, segments);
+
+        let mut snapshot = self.snapshot.write().unwrap_or_else(|e| e.into_inner());
+        snapshot.site.update(site, "site");
+        for (id, result) in self.segments.iter().zip(segments) {
+            snapshot.segments.entry(id.clone()).or_default().update(result, "segment");
+        }
+        snapshot.last_poll = Some(SystemTime::now());
+    }
+
+    /// Start a background task that polls immediately and then on every interval
+    ///
+    /// Must be called from within a tokio runtime. Abort the returned handle to stop polling.
+    pub fn spawn_poller(&self) -> JoinHandle<()> {
+        let exporter = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(exporter.interval);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                exporter.poll().await;
+            }
+        })
+    }
+
+    /// Render the latest values in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let snapshot = self.snapshot.read().unwrap_or_else(|e| e.into_inner());
+        let mut out = String::new();
+
+        let site = snapshot.site.stats.as_ref();
+        gauge(&mut out, SITE_USERS, "Number of people known to the site", site.map(|s| ((), s.user_count)));
+        gauge(&mut out, SITE_SUBSCRIBERS, "Number of active subscribers on the site", site.map(|s| ((), s.subscriber_count)));
+        gauge(&mut out, SITE_UNSUBSCRIBERS, "Number of unsubscribed people on the site", site.map(|s| ((), s.unsubscriber_count)));
+
+        let segments = || snapshot.segments.iter().filter_map(|(id, t)| t.stats.as_ref().map(|s| (id.as_str(), s)));
+        gauge(&mut out, SEGMENT_USERS, "Number of people in a segment", segments().map(|(id, s)| (id, s.user_count)));
+        gauge(&mut out, SEGMENT_SUBSCRIBERS, "Number of active subscribers in a segment", segments().map(|(id, s)| (id, s.subscriber_count)));
+        gauge(&mut out, SEGMENT_UNSUBSCRIBERS, "Number of unsubscribed people in a segment", segments().map(|(id, s)| (id, s.unsubscriber_count)));
+
+        let _ = writeln!(out, "# HELP {} Whether the latest poll of a target succeeded", UP);
+        let _ = writeln!(out, "# TYPE {} gauge", UP);
+        if snapshot.last_poll.is_some() {
+            let _ = writeln!(out, "{}{{target=\"site\",segment_id=\"\"}} {}", UP, u8::from(snapshot.site.up));
+        }
+        for (id, target) in &snapshot.segments {
+            let _ = writeln!(out, "{}{{target=\"segment\",segment_id=\"{}\"}} {}", UP, escape(id), u8::from(target.up));
+        }
+
+        let last_poll = snapshot.last_poll
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|since| ((), since.as_secs_f64()));
+        gauge(&mut out, LAST_POLL_TIMESTAMP_SECONDS, "Unix time of the latest poll", last_poll);
+
+        out
+    }
+
+    /// Create a router that serves [`render`](Self::render) on `GET /metrics`
+    ///
+    /// The router does not poll; pair it with [`spawn_poller`](Self::spawn_poller).
+    pub fn router<S>(&self) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        let exporter = self.clone();
+        Router::new().route("/metrics", get(move || {
+            let body = exporter.render();
+            async move { ([(CONTENT_TYPE, TEXT_FORMAT)], body) }
+        }))
+    }
+
+    /// Poll in the background and serve `/metrics` on `listener` until the server fails
+    pub async fn serve(self, listener: TcpListener) -> std::io::Result<()> {
+        let poller = self.spawn_poller();
+        let result = ::axum::serve(listener, self.router::<()>()).await;
+        poller.abort();
+        result
+    }
+}
+
+/// Builder for [`StatsExporter`]
+pub struct StatsExporterBuilder {
+    client: Client,
+    segments: Vec<String>,
+    interval: Duration,
+}
+
+impl fmt::Debug for StatsExporterBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StatsExporterBuilder")
+            .field("segments", &self.segments)
+            .field("interval", &self.interval)
+            .finish_non_exhaustive()
+    }
+}
+
+impl StatsExporterBuilder {
+    /// Add a segment to poll
+    pub fn segment(mut self, segment_id: impl Into<String>) -> Self {
+        self.segments.push(segment_id.into());
+        self
+    }
+
+    /// Add several segments to poll
+    pub fn segments<I, S>(mut self, segment_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.segments.extend(segment_ids.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set the time between polls (default 60s)
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Build the exporter
+    ///
+    /// # Errors
+    /// * `Error::InvalidSegmentId` if a segment ID is empty
+    /// * `Error::InvalidConfig` if the interval is zero
+    pub fn build(mut self) -> Result<StatsExporter> {
+        if self.segments.iter().any(String::is_empty) {
+            return Err(Error::InvalidSegmentId("Segment ID is required".into()));
+        }
+        if self.interval.is_zero() {
+            return Err(Error::InvalidConfig("poll interval must be positive".into()));
+        }
+        self.segments.sort();
+        self.segments.dedup();
+
+        Ok(StatsExporter {
+            client: self.client,
+            segments: self.segments.into(),
+            interval: self.interval,
+            snapshot: Arc::default(),
+        })
+    }
+}
+
+/// Labels attached to a gauge sample
+trait Labels {
+    fn write(&self, out: &mut String);
+}
+
+impl Labels for () {
+    fn write(&self, _: &mut String) {}
+}
+
+impl Labels for &str {
+    fn write(&self, out: &mut String) {
+        let _ = write!(out, "{{segment_id=\"{}\"}}", escape(self));
+    }
+}
+
+/// Writes one gauge family; the HELP and TYPE lines are written even without samples.
+fn gauge<L, V>(out: &mut String, name: &str, help: &str, samples: impl IntoIterator<Item = (L, V)>)
+where
+    L: Labels,
+    V: fmt::Display,
+{
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} gauge", name);
+    for (labels, value) in samples {
+        out.push_str(name);
+        labels.write(out);
+        let _ = writeln!(out, " {}", value);
+    }
+}
+
+/// Escapes a label value for the text exposition format
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::FakeBento;
+    use crate::{HttpResponse, ImportSubscriberData, Method};
+    use ::axum::body::{to_bytes, Body};
+    use ::axum::http::{Request, StatusCode};
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    fn exporter(bento: &FakeBento) -> StatsExporter {
+        StatsExporter::builder(bento.client())
+            .segments(["seg_b", "seg_a"])
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_poll_and_render() {
+        let bento = FakeBento::new();
+        bento.add_subscriber(ImportSubscriberData {
+            email: "user@example.com".parse().unwrap(),
+            first_name: None,
+            last_name: None,
+            tags: None,
+            remove_tags: None,
+            custom_fields: Default::default(),
+        });
+        bento.stub(
+            Method::Get,
+            "/stats/segment",
+            HttpResponse::json_body(200, &json!({
+                "user_count": 40,
+                "subscriber_count": 35,
+                "unsubscriber_count": 5
+            })).unwrap(),
+        );
+
+        let exporter = exporter(&bento);
+        assert!(!exporter.render().contains("\nbento_site_users "));
+
+        exporter.poll().await;
+        let text = exporter.render();
+
+        assert!(text.contains("# TYPE bento_site_users gauge\nbento_site_users 1\n"));
+        assert!(text.contains("bento_site_subscribers 1\n"));
+        assert!(text.contains("bento_site_unsubscribers 0\n"));
+        assert!(text.contains(
+            "bento_segment_users{segment_id=\"seg_a\"} 40\nbento_segment_users{segment_id=\"seg_b\"} 40\n"
+        ));
+        assert!(text.contains("bento_segment_unsubscribers{segment_id=\"seg_b\"} 5\n"));
+        assert!(text.contains("bento_stats_up{target=\"site\",segment_id=\"\"} 1\n"));
+        assert!(text.contains("bento_stats_up{target=\"segment\",segment_id=\"seg_a\"} 1\n"));
+        assert!(text.contains("bento_stats_last_poll_timestamp_seconds "));
+    }
+
+    #[tokio::test]
+    async fn test_failed_poll_keeps_last_values() {
+        let bento = FakeBento::new();
+        let exporter = StatsExporter::builder(bento.client()).build().unwrap();
+
+        exporter.poll().await;
+        bento.fail_next(503);
+        exporter.poll().await;
+        let text = exporter.render();
+
+        assert!(text.contains("bento_site_users 0\n"));
+        assert!(text.contains("bento_stats_up{target=\"site\",segment_id=\"\"} 0\n"));
+    }
+
+    #[tokio::test]
+    async fn test_router_serves_metrics() {
+        let bento = FakeBento::new();
+        let exporter = exporter(&bento);
+        exporter.poll().await;
+
+        let app: Router = exporter.router();
+        let response = app.oneshot(Request::get("/metrics").body(Body::empty()).unwrap()).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], TEXT_FORMAT);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("bento_segment_users{segment_id=\"seg_b\"} 0\n"));
+        assert!(body.contains("bento_stats_up{target=\"segment\",segment_id=\"seg_b\"} 1\n"));
+    }
+
+    #[test]
+    fn test_builder_validation() {
+        let client = FakeBento::new().client();
+
+        let result = StatsExporter::builder(client.clone()).segment("").build();
+        assert!(matches!(result, Err(Error::InvalidSegmentId(_))));
+
+        let result = StatsExporter::builder(client).interval(Duration::ZERO).build();
+        assert!(matches!(result, Err(Error::InvalidConfig(_))));
+
+        assert_eq!(escape("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+}
@@ -0,0 +1,124 @@
+//! Client-side rate limiting.
+
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+/// Number of requests allowed per time window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Quota {
+    pub(crate) requests: u32,
+    pub(crate) per: Duration,
+}
+
+/// Token bucket limiting how fast requests are sent
+///
+/// The bucket holds up to `quota.requests` tokens and refills continuously at
+/// `quota.requests / quota.per`. Waiters are served in FIFO order because the
+/// lock is held while waiting for a token.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    quota: Quota,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// Create a limiter with a full bucket
+    pub(crate) fn new(quota: Quota) -> Self {
+        Self {
+            quota,
+            bucket: Mutex::new(Bucket {
+                tokens: f64::from(quota.requests),
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// Wait until a token is available and take it
+    ///
+    /// Returns true if the caller had to wait for capacity.
+    pub(crate) async fn acquire(&self) -> bool {
+        let mut bucket = self.bucket.lock().await;
+        self.refill(&mut bucket);
+
+        let waited = bucket.tokens < 1.0;
+        if waited {
+            let wait = Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate());
+            tokio::time::sleep(wait).await;
+            self.refill(&mut bucket);
+        }
+
+        bucket.tokens = (bucket.tokens - 1.0).max(0.0);
+        waited
+    }
+
+    /// Tokens added per second
+    fn rate(&self) -> f64 {
+        f64::from(self.quota.requests) / self.quota.per.as_secs_f64()
+    }
+
+    fn refill(&self, bucket: &mut Bucket) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate()).min(f64::from(self.quota.requests));
+        bucket.last_refill = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_burst_then_wait() {
+        let limiter = RateLimiter::new(Quota { requests: 2, per: Duration::from_secs(1) });
+        let started = Instant::now();
+
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert_eq!(started.elapsed(), Duration::ZERO);
+
+        limiter.acquire().await;
+        assert_eq!(started.elapsed(), Duration::from_millis(500));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_refills_over_time() {
+        let limiter = RateLimiter::new(Quota { requests: 1, per: Duration::from_secs(1) });
+
+        limiter.acquire().await;
+        tokio::time::sleep(Duration::from_secs(5)).await;
+
+        // Idle time never accumulates beyond the bucket capacity
+        let started = Instant::now();
+        limiter.acquire().await;
+        assert_eq!(started.elapsed(), Duration::ZERO);
+        limiter.acquire().await;
+        assert_eq!(started.elapsed(), Duration::from_secs(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_shared_between_tasks() {
+        let limiter = Arc::new(RateLimiter::new(Quota { requests: 10, per: Duration::from_secs(1) }));
+        let started = Instant::now();
+
+        let tasks: Vec<_> = (0..30)
+            .map(|_| {
+                let limiter = Arc::clone(&limiter);
+                tokio::spawn(async move { limiter.acquire().await })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        assert_eq!(started.elapsed(), Duration::from_secs(2));
+    }
+}
\ No newline at end of file
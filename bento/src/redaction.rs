@@ -0,0 +1,113 @@
+//! Redaction of personal data recorded in tracing spans.
+
+use crate::Error;
+use hmac::{Hmac, Mac};
+use secrecy::{ExposeSecret, SecretString};
+use sha2::Sha256;
+
+/// How subscriber emails are recorded in tracing spans
+///
+/// Spans never record request bodies, so event details, custom fields and email
+/// content are not logged in any mode; this setting only controls the email
+/// addresses attached to single-subscriber calls.
+#[derive(Debug, Clone, Default)]
+pub enum Redaction {
+    /// Record a truncated HMAC-SHA256 of the normalized address under `key`, e.g.
+    /// `hmac:1dda40a9f38c9c33`, so one subscriber can be followed across logs
+    ///
+    /// This is pseudonymization, not anonymization: anyone holding the key can test
+    /// candidate addresses against the logs. Keep the key secret and use a different
+    /// key per service so logs from different services cannot be linked.
+    Hash {
+        /// Secret key for the HMAC
+        key: SecretString,
+    },
+    /// Keep the first character and the domain, e.g. `j***@example.com`
+    #[default]
+    Mask,
+    /// Record addresses unchanged
+    Keep,
+}
+
+impl PartialEq for Redaction {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Redaction::Hash { key: a }, Redaction::Hash { key: b }) => a.expose_secret() == b.expose_secret(),
+            (Redaction::Mask, Redaction::Mask) | (Redaction::Keep, Redaction::Keep) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Redaction {
+    /// Create a [`Redaction::Hash`] mode with the given HMAC key
+    pub fn hash(key: impl Into<String>) -> Self {
+        Redaction::Hash { key: SecretString::new(key.into()) }
+    }
+
+    /// Redact an email address according to this mode
+    pub fn email(&self, email: &str) -> String {
+        match self {
+            Redaction::Hash { key } => {
+                let mut mac = Hmac::<Sha256>::new_from_slice(key.expose_secret().as_bytes())
+                    .expect("HMAC accepts keys of any length");
+                mac.update(email.trim().to_lowercase().as_bytes());
+                format!("hmac:{}", hex::encode(&mac.finalize().into_bytes()[..8]))
+            }
+            Redaction::Mask => match email.rsplit_once('@') {
+                Some((local, domain)) => {
+                    let first: String = local.chars().take(1).collect();
+                    format!("{}***@{}", first, domain)
+                }
+                None => "***".to_string(),
+            },
+            Redaction::Keep => email.to_string(),
+        }
+    }
+
+    /// Parses a mode name from configuration; `hash` requires a non-empty key.
+    pub(crate) fn parse(mode: &str, key: Option<SecretString>) -> Result<Self, Error> {
+        match mode.to_ascii_lowercase().as_str() {
+            "hash" => match key {
+                Some(key) if !key.expose_secret().is_empty() => Ok(Redaction::Hash { key }),
+                _ => Err(Error::InvalidConfig("hash redaction requires a redaction key".into())),
+            },
+            "mask" => Ok(Redaction::Mask),
+            "keep" => Ok(Redaction::Keep),
+            other => Err(Error::InvalidConfig(format!(
+                "unknown redaction mode '{}', expected hash, mask or keep",
+                other
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_email() {
+        assert_eq!(Redaction::Mask.email("jesse@example.com"), "j***@example.com");
+        assert_eq!(Redaction::Mask.email("not-an-email"), "***");
+        assert_eq!(Redaction::Keep.email("jesse@example.com"), "jesse@example.com");
+
+        let hashed = Redaction::hash("test-key").email("jesse@example.com");
+        assert_eq!(hashed, "hmac:1dda40a9f38c9c33");
+        assert_eq!(Redaction::hash("test-key").email(" Jesse@Example.com"), hashed);
+        assert_ne!(Redaction::hash("other-key").email("jesse@example.com"), hashed);
+        assert!(!hashed.contains("jesse"));
+        assert!(!format!("{:?}", Redaction::hash("test-key")).contains("test-key"));
+    }
+
+    #[test]
+    fn test_parse_redaction() {
+        let key = || Some(SecretString::new("test-key".into()));
+        assert_eq!(Redaction::parse("hash", key()).unwrap(), Redaction::hash("test-key"));
+        assert_eq!(Redaction::parse("MASK", None).unwrap(), Redaction::Mask);
+        assert_eq!(Redaction::parse("keep", None).unwrap(), Redaction::Keep);
+        assert!(matches!(Redaction::parse("hash", None), Err(Error::InvalidConfig(_))));
+        assert!(matches!(Redaction::parse("hash", Some(SecretString::new(String::new()))), Err(Error::InvalidConfig(_))));
+        assert!(matches!(Redaction::parse("none", None), Err(Error::InvalidConfig(_))));
+    }
+}
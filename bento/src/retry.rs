@@ -0,0 +1,300 @@
+//! Retry helpers used by the client when a request fails transiently.
+
+use rand::Rng;
+use crate::{Error, Method};
+use std::time::Duration;
+use time::format_description::well_known::Rfc2822;
+use time::OffsetDateTime;
+
+/// Endpoints whose POST requests are not safe to repeat
+///
+/// Replaying one of these after an ambiguous failure can record duplicate events,
+/// apply commands twice or send the same email again.
+const NON_IDEMPOTENT_PATHS: [&str; 3] = ["/batch/events", "/fetch/commands", "/batch/emails"];
+
+/// Policy controlling how the client retries transient failures
+///
+/// # Example
+/// ```
+/// use bento::{ConfigBuilder, RetryPolicy};
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::new()
+///     .max_retries(5)
+///     .base_delay(Duration::from_millis(250))
+///     .max_delay(Duration::from_secs(30))
+///     .max_retry_after(Duration::from_secs(120))
+///     .retry_non_idempotent(true);
+///
+/// let config = ConfigBuilder::new()
+///     .publishable_key("pub_key")
+///     .secret_key("secret_key")
+///     .site_uuid("site_uuid")
+///     .retry_policy(policy)
+///     .build();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    pub(crate) max_retries: u32,
+    pub(crate) base_delay: Duration,
+    pub(crate) max_delay: Duration,
+    pub(crate) max_retry_after: Duration,
+    pub(crate) jitter: f64,
+    pub(crate) retry_statuses: Vec<u16>,
+    pub(crate) retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(5),
+            max_retry_after: Duration::from_secs(60),
+            jitter: 0.5,
+            retry_statuses: vec![429, 502, 503, 504],
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Create a policy with the default settings
+    ///
+    /// Defaults to 3 retries, a 100ms base delay capped at 5s, 50% jitter, waiting up to
+    /// 60s when asked to by `Retry-After`, and retrying 429, 502, 503 and 504 responses.
+    /// Non-idempotent POSTs are only retried on a 429 unless
+    /// [`retry_non_idempotent`](Self::retry_non_idempotent) is enabled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a policy that never retries
+    pub fn none() -> Self {
+        Self::default().max_retries(0)
+    }
+
+    /// Set the maximum number of retries after the initial attempt
+    pub fn max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// Set the delay before the first retry
+    ///
+    /// The delay doubles on every subsequent retry.
+    pub fn base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    /// Set the upper bound for any single backoff delay between attempts
+    ///
+    /// Delays requested through the `Retry-After` header are not capped by this
+    /// setting; see [`max_retry_after`](Self::max_retry_after).
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Set the longest `Retry-After` delay the client will wait for
+    ///
+    /// When the server asks for a longer delay, the response is returned instead of
+    /// being retried early.
+    pub fn max_retry_after(mut self, delay: Duration) -> Self {
+        self.max_retry_after = delay;
+        self
+    }
+
+    /// Set the jitter as a fraction of the computed delay
+    ///
+    /// Each delay is reduced by a random amount of up to this fraction. The value is
+    /// clamped to `0.0..=1.0`; `0.0` disables jitter.
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the HTTP status codes that are retried
+    pub fn retry_statuses(mut self, statuses: impl IntoIterator<Item = u16>) -> Self {
+        self.retry_statuses = statuses.into_iter().collect();
+        self
+    }
+
+    /// Set whether non-idempotent POSTs may be retried (default false)
+    ///
+    /// Applies to `/batch/events`, `/fetch/commands` and `/batch/emails`. When disabled,
+    /// these requests are only retried on a 429 response, which guarantees the API did
+    /// not process them. Enabling it can record duplicate events, apply commands twice
+    /// or send the same email again after a 5xx or a timeout.
+    pub fn retry_non_idempotent(mut self, allow: bool) -> Self {
+        self.retry_non_idempotent = allow;
+        self
+    }
+
+    /// Returns true if a response with this status should be retried
+    pub(crate) fn is_retryable_status(&self, status: u16, idempotent: bool) -> bool {
+        if !idempotent && !self.retry_non_idempotent {
+            return status == 429 && self.retry_statuses.contains(&status);
+        }
+        self.retry_statuses.contains(&status)
+    }
+
+    /// Returns true if a transport error should be retried
+    ///
+    /// Only connection failures and timeouts are considered transient. A timed out
+    /// non-idempotent request may have been processed, so it is only retried when
+    /// `retry_non_idempotent` allows it.
+    pub(crate) fn is_retryable_error(&self, err: &Error, idempotent: bool) -> bool {
+        let (connect, timeout) = match err {
+            Error::HttpClient(e) => (e.is_connect(), e.is_timeout()),
+            Error::Connection(_) => (true, false),
+            Error::Timeout(_) => (false, true),
+            _ => (false, false),
+        };
+        if !idempotent && !self.retry_non_idempotent {
+            return connect;
+        }
+        connect || timeout
+    }
+
+    /// Computes the exponential backoff delay for a zero-based retry attempt
+    ///
+    /// The delay doubles on every attempt, is capped at `max_delay`, and is then reduced
+    /// by a random amount according to `jitter` so that concurrent clients spread out.
+    pub(crate) fn backoff_delay(&self, attempt: u32) -> Duration {
+        let exponential = self.base_delay
+            .checked_mul(2u32.saturating_pow(attempt))
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay);
+        if self.jitter == 0.0 {
+            return exponential;
+        }
+        let jitter = rand::thread_rng().gen_range(0.0..=self.jitter);
+        exponential.mul_f64(1.0 - jitter)
+    }
+}
+
+/// Returns true if repeating this request cannot cause duplicate side effects
+pub(crate) fn is_idempotent(method: Method, path: &str) -> bool {
+    method != Method::Post || !NON_IDEMPOTENT_PATHS.iter().any(|p| path.ends_with(p))
+}
+
+/// Reads the delay requested by the server through the `Retry-After` header
+///
+/// Both the delay-seconds and HTTP-date forms are supported. Dates in the past
+/// yield a zero delay.
+pub(crate) fn retry_after(value: Option<&str>) -> Option<Duration> {
+    let value = value?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = OffsetDateTime::parse(value, &Rfc2822).ok()?;
+    let remaining = date - OffsetDateTime::now_utc();
+    Some(Duration::try_from(remaining).unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_retryable_statuses() {
+        let policy = RetryPolicy::default();
+        assert!(policy.is_retryable_status(429, true));
+        assert!(policy.is_retryable_status(502, true));
+        assert!(policy.is_retryable_status(503, true));
+        assert!(policy.is_retryable_status(504, true));
+        assert!(!policy.is_retryable_status(500, true));
+        assert!(!policy.is_retryable_status(400, true));
+        assert!(!policy.is_retryable_status(200, true));
+    }
+
+    #[test]
+    fn test_custom_retryable_statuses() {
+        let policy = RetryPolicy::new().retry_statuses([500]);
+        assert!(policy.is_retryable_status(500, true));
+        assert!(!policy.is_retryable_status(429, true));
+    }
+
+    #[test]
+    fn test_non_idempotent_requests() {
+        assert!(!is_idempotent(Method::Post, "/api/v1/batch/events"));
+        assert!(!is_idempotent(Method::Post, "/fetch/commands"));
+        assert!(!is_idempotent(Method::Post, "/batch/emails"));
+        assert!(is_idempotent(Method::Post, "/batch/subscribers"));
+        assert!(is_idempotent(Method::Get, "/fetch/subscribers"));
+
+        let policy = RetryPolicy::new();
+        assert!(policy.is_retryable_status(429, false));
+        assert!(!policy.is_retryable_status(503, false));
+        assert!(policy.is_retryable_status(503, true));
+        assert!(!policy.is_retryable_error(&Error::Timeout("read".into()), false));
+
+        let policy = RetryPolicy::new().retry_non_idempotent(true);
+        assert!(policy.is_retryable_status(503, false));
+        assert!(policy.is_retryable_error(&Error::Timeout("read".into()), false));
+    }
+
+    #[test]
+    fn test_backoff_delay_bounds() {
+        let policy = RetryPolicy::default();
+        for attempt in 0..10 {
+            let delay = policy.backoff_delay(attempt);
+            let ceiling = policy.base_delay.saturating_mul(2u32.pow(attempt)).min(policy.max_delay);
+            assert!(delay <= ceiling, "attempt {}: {:?} > {:?}", attempt, delay, ceiling);
+            assert!(delay >= ceiling / 2, "attempt {}: {:?} < {:?}", attempt, delay, ceiling / 2);
+        }
+
+        assert!(policy.backoff_delay(u32::MAX) <= policy.max_delay);
+    }
+
+    #[test]
+    fn test_backoff_delay_without_jitter() {
+        let policy = RetryPolicy::new()
+            .base_delay(Duration::from_millis(10))
+            .max_delay(Duration::from_millis(50))
+            .jitter(0.0);
+
+        assert_eq!(policy.backoff_delay(0), Duration::from_millis(10));
+        assert_eq!(policy.backoff_delay(1), Duration::from_millis(20));
+        assert_eq!(policy.backoff_delay(2), Duration::from_millis(40));
+        assert_eq!(policy.backoff_delay(3), Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_retryable_errors() {
+        let policy = RetryPolicy::default();
+        assert!(policy.is_retryable_error(&Error::Connection("refused".into()), true));
+        assert!(policy.is_retryable_error(&Error::Timeout("30s".into()), true));
+        assert!(!policy.is_retryable_error(&Error::InvalidRequest("bad".into()), true));
+
+        let policy = RetryPolicy::new().retry_non_idempotent(false);
+        assert!(policy.is_retryable_error(&Error::Connection("refused".into()), false));
+        assert!(!policy.is_retryable_error(&Error::Timeout("30s".into()), false));
+    }
+
+    #[test]
+    fn test_retry_after_seconds() {
+        assert_eq!(retry_after(Some("7")), Some(Duration::from_secs(7)));
+    }
+
+    #[test]
+    fn test_retry_after_http_date() {
+        assert_eq!(retry_after(Some("Sun, 06 Nov 1994 08:49:37 GMT")), Some(Duration::ZERO));
+
+        let future = (OffsetDateTime::now_utc() + time::Duration::seconds(30))
+            .format(&Rfc2822)
+            .unwrap();
+        let delay = retry_after(Some(&future)).unwrap();
+        assert!(delay > Duration::from_secs(25) && delay <= Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_retry_after_missing_or_invalid() {
+        assert_eq!(retry_after(None), None);
+        assert_eq!(retry_after(Some("soon")), None);
+    }
+}
\ No newline at end of file
@@ -1,52 +1,111 @@
 use crate::{Client, Error, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
 use tracing::instrument;
 
+/// Subscriber counts for the whole site
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SiteStats {
+    /// Number of people known to the site
+    pub user_count: u64,
+    /// Number of active subscribers
+    pub subscriber_count: u64,
+    /// Number of unsubscribed people
+    pub unsubscriber_count: u64,
+    /// Keys not covered by the fields above
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// Subscriber counts for one segment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SegmentStats {
+    /// Number of people in the segment
+    pub user_count: u64,
+    /// Number of active subscribers in the segment
+    pub subscriber_count: u64,
+    /// Number of unsubscribed people in the segment
+    pub unsubscriber_count: u64,
+    /// Keys not covered by the fields above
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// Results of a saved report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportStats {
+    /// Report contents
+    pub report_data: ReportData,
+    /// Keys not covered by the fields above
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// Contents of a saved report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportData {
+    /// Report values; the shape depends on the report type
+    #[serde(default)]
+    pub data: Value,
+    /// How the report is charted, e.g. `count`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chart_style: Option<String>,
+    /// Report type, e.g. `Reporting::Reports::VisitorCountReport`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub report_type: Option<String>,
+    /// Name of the report
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub report_name: Option<String>,
+    /// Keys not covered by the fields above
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
 impl Client {
     /// Get site-wide statistics
-    #[instrument(skip(self))]
-    pub async fn get_site_stats(&self) -> Result<serde_json::Value> {
+    #[instrument(skip_all)]
+    pub async fn get_site_stats(&self) -> Result<SiteStats> {
         let url = self.build_url("/stats/site")?;
         let response = self.request(
-            self.http_client.get(&url)
+            self.get(&url)
         ).await?;
 
-        let stats = response.json().await?;
+        let stats = response.json()?;
         Ok(stats)
     }
 
     /// Get statistics for a specific segment
-    #[instrument(skip(self))]
-    pub async fn get_segment_stats(&self, segment_id: &str) -> Result<serde_json::Value> {
+    #[instrument(skip_all, fields(segment_id = %segment_id))]
+    pub async fn get_segment_stats(&self, segment_id: &str) -> Result<SegmentStats> {
         if segment_id.is_empty() {
             return Err(Error::InvalidSegmentId("Segment ID is required".into()));
         }
 
         let url = self.build_url("/stats/segment")?;
         let response = self.request(
-            self.http_client
-                .get(&url)
+            self.get(&url)
                 .query(&[("segment_id", segment_id)])
         ).await?;
 
-        let stats = response.json().await?;
+        let stats = response.json()?;
         Ok(stats)
     }
 
     /// Get statistics for a specific report
-    #[instrument(skip(self))]
-    pub async fn get_report_stats(&self, report_id: &str) -> Result<serde_json::Value> {
+    #[instrument(skip_all, fields(report_id = %report_id))]
+    pub async fn get_report_stats(&self, report_id: &str) -> Result<ReportStats> {
         if report_id.is_empty() {
             return Err(Error::InvalidRequest("Report ID is required".into()));
         }
 
         let url = self.build_url("/stats/report")?;
         let response = self.request(
-            self.http_client
-                .get(&url)
+            self.get(&url)
                 .query(&[("report_id", report_id)])
         ).await?;
 
-        let stats = response.json().await?;
+        let stats = response.json()?;
         Ok(stats)
     }
 }
@@ -65,17 +124,21 @@ mod tests {
             .and(path("/stats/site"))
             .respond_with(ResponseTemplate::new(200)
                 .set_body_json(serde_json::json!({
-                    "total_subscribers": 1000,
-                    "active_subscribers": 950,
+                    "user_count": 1000,
+                    "subscriber_count": 950,
+                    "unsubscriber_count": 50,
                     "growth_rate": 5.5
                 })))
             .mount(&mock_server)
             .await;
 
         let client = crate::test_utils::create_test_client(mock_server.uri());
-        let result = client.get_site_stats().await;
+        let stats = client.get_site_stats().await.unwrap();
 
-        assert!(result.is_ok());
+        assert_eq!(stats.user_count, 1000);
+        assert_eq!(stats.subscriber_count, 950);
+        assert_eq!(stats.unsubscriber_count, 50);
+        assert_eq!(stats.extra["growth_rate"], 5.5);
     }
 
     #[tokio::test]
@@ -87,16 +150,20 @@ mod tests {
             .and(query_param("segment_id", "segment_123"))
             .respond_with(ResponseTemplate::new(200)
                 .set_body_json(serde_json::json!({
-                    "segment_size": 500,
-                    "conversion_rate": 25.5
+                    "user_count": 500,
+                    "subscriber_count": 480,
+                    "unsubscriber_count": 20
                 })))
             .mount(&mock_server)
             .await;
 
         let client = crate::test_utils::create_test_client(mock_server.uri());
-        let result = client.get_segment_stats("segment_123").await;
+        let stats = client.get_segment_stats("segment_123").await.unwrap();
 
-        assert!(result.is_ok());
+        assert_eq!(stats.user_count, 500);
+        assert_eq!(stats.subscriber_count, 480);
+        assert_eq!(stats.unsubscriber_count, 20);
+        assert!(stats.extra.is_empty());
     }
 
     #[tokio::test]
@@ -108,16 +175,42 @@ mod tests {
             .and(query_param("report_id", "report_123"))
             .respond_with(ResponseTemplate::new(200)
                 .set_body_json(serde_json::json!({
-                    "total_views": 1000,
-                    "unique_views": 750
+                    "report_data": {
+                        "data": { "2024-01-01": 12, "2024-01-02": 7 },
+                        "chart_style": "count",
+                        "report_type": "Reporting::Reports::VisitorCountReport",
+                        "report_name": "New Subscribers",
+                        "timezone": "UTC"
+                    }
                 })))
             .mount(&mock_server)
             .await;
 
         let client = crate::test_utils::create_test_client(mock_server.uri());
-        let result = client.get_report_stats("report_123").await;
+        let stats = client.get_report_stats("report_123").await.unwrap();
+
+        assert_eq!(stats.report_data.data["2024-01-01"], 12);
+        assert_eq!(stats.report_data.chart_style.as_deref(), Some("count"));
+        assert_eq!(stats.report_data.report_type.as_deref(), Some("Reporting::Reports::VisitorCountReport"));
+        assert_eq!(stats.report_data.report_name.as_deref(), Some("New Subscribers"));
+        assert_eq!(stats.report_data.extra["timezone"], "UTC");
+    }
+
+    #[tokio::test]
+    async fn test_stats_unexpected_shape() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/stats/site"))
+            .respond_with(ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "total_subscribers": 1000 })))
+            .mount(&mock_server)
+            .await;
+
+        let client = crate::test_utils::create_test_client(mock_server.uri());
+        let result = client.get_site_stats().await;
 
-        assert!(result.is_ok());
+        assert!(matches!(result, Err(Error::Json(_))));
     }
 
     #[tokio::test]
@@ -1,81 +1,244 @@
-use crate::{ApiResponse, Client, CreateSubscriberRequest, CreateSubscriberData, Error, ImportSubscriberData, ImportSubscriberResponse, Result, SubscriberData};
-use tracing::instrument;
+use crate::{ApiResponse, BatchOutcome, Client, CreateSubscriberRequest, CreateSubscriberData, EmailAddress, Error, ImportSubscriberData, Result, SubscriberData};
+use futures::stream::{self, StreamExt};
+use std::time::Duration;
+use tracing::{instrument, warn};
+
+/// Maximum number of subscribers accepted by `/batch/subscribers` in one request
+const MAX_IMPORT_BATCH_SIZE: usize = 1000;
+
+/// Options for [`Client::import_subscribers_bulk`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BulkImportOptions {
+    chunk_size: usize,
+    concurrency: usize,
+    chunk_retries: u32,
+    retry_delay: Duration,
+}
+
+impl Default for BulkImportOptions {
+    fn default() -> Self {
+        Self {
+            chunk_size: MAX_IMPORT_BATCH_SIZE,
+            concurrency: 4,
+            chunk_retries: 2,
+            retry_delay: Duration::from_secs(1),
+        }
+    }
+}
+
+impl BulkImportOptions {
+    /// Create options with the defaults
+    ///
+    /// Defaults to chunks of 1000 subscribers, 4 chunks in flight, and 2 retries per
+    /// chunk starting 1s apart.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the number of subscribers sent per request (at most 1000)
+    pub fn chunk_size(mut self, size: usize) -> Self {
+        self.chunk_size = size;
+        self
+    }
+
+    /// Set the number of chunks sent concurrently
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Set how many times a chunk that failed with a retryable error is sent again
+    ///
+    /// These retries happen after the client's own [`RetryPolicy`](crate::RetryPolicy)
+    /// has given up on the request.
+    pub fn chunk_retries(mut self, retries: u32) -> Self {
+        self.chunk_retries = retries;
+        self
+    }
+
+    /// Set the delay before the first chunk retry; it doubles on every retry
+    pub fn retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+}
+
+/// A chunk that could not be imported
+#[derive(Debug)]
+pub struct FailedChunk {
+    /// Zero-based position of the chunk in the input
+    pub index: usize,
+    /// Subscribers in the chunk, so they can be retried later
+    pub subscribers: Vec<ImportSubscriberData>,
+    /// Error from the last attempt
+    pub error: Error,
+}
+
+/// Aggregated outcome of [`Client::import_subscribers_bulk`]
+#[derive(Debug, Default)]
+pub struct BulkImportSummary {
+    /// Combined outcome of every chunk
+    ///
+    /// `failed` includes both rows the API rejected and every row of a chunk in
+    /// `failed_chunks`. Error indexes are positions in the full input.
+    pub outcome: BatchOutcome,
+    /// Chunks whose request failed entirely
+    pub failed_chunks: Vec<FailedChunk>,
+}
 
 impl Client {
     /// Find a subscriber by email
-    #[instrument(skip(self))]
+    #[instrument(skip_all, fields(email = %self.redact(email)))]
     pub async fn find_subscriber(&self, email: &str) -> Result<SubscriberData> {
-        if !email.contains('@') {
-            return Err(Error::InvalidEmail(email.to_string()));
-        }
+        let email = EmailAddress::parse(email)?;
 
         let url = self.build_url("/fetch/subscribers")?;
         let response = self.request(
-            self.http_client
-                .get(&url)
-                .query(&[("email", email)])
+            self.get(&url)
+                .query(&[("email", email.as_str())])
         ).await?;
 
-        let api_response: ApiResponse<SubscriberData> = response.json().await?;
+        let api_response: ApiResponse<SubscriberData> = response.json()?;
         Ok(api_response.data)
     }
 
     /// Create a new subscriber with just email
-    #[instrument(skip(self))]
+    #[instrument(skip_all, fields(email = %self.redact(email)))]
     pub async fn create_subscriber(&self, email: &str) -> Result<SubscriberData> {
-        if !email.contains('@') {
-            return Err(Error::InvalidEmail(email.to_string()));
-        }
+        let email = EmailAddress::parse(email)?;
 
         let url = self.build_url("/fetch/subscribers")?;
         let request = CreateSubscriberRequest {
             subscriber: CreateSubscriberData {
-                email: email.to_string(),
+                email,
             }
         };
 
         let response = self.request(
-            self.http_client
-                .post(&url)
+            self.post(&url)
                 .json(&request)
         ).await?;
 
-        let api_response: ApiResponse<SubscriberData> = response.json().await?;
+        let api_response: ApiResponse<SubscriberData> = response.json()?;
         Ok(api_response.data)
     }
 
     /// Import multiple subscribers with full data
-    #[instrument(skip(self))]
-    pub async fn import_subscribers(&self, subscribers: Vec<ImportSubscriberData>) -> Result<()> {
+    ///
+    /// Rows the API rejects are reported in the returned [`BatchOutcome`] rather than
+    /// as an error.
+    #[instrument(skip_all, fields(count = subscribers.len()))]
+    pub async fn import_subscribers(&self, subscribers: Vec<ImportSubscriberData>) -> Result<BatchOutcome> {
         if subscribers.is_empty() {
             return Err(Error::InvalidRequest("No subscribers provided".into()));
         }
 
-        for subscriber in &subscribers {
-            if !subscriber.email.contains('@') {
-                return Err(Error::InvalidEmail(subscriber.email.clone()));
+        self.import_chunk(&subscribers).await
+    }
+
+    /// Import any number of subscribers in concurrent, API-sized chunks
+    ///
+    /// Chunks that fail with a retryable error are retried according to `options`;
+    /// chunks that still fail are reported in the summary rather than aborting the
+    /// import.
+    ///
+    /// # Errors
+    /// * `Error::InvalidRequest` if no subscribers are provided or the concurrency is 0
+    /// * `Error::InvalidBatchSize` if the chunk size is 0 or above 1000
+    #[instrument(skip_all, fields(count = subscribers.len()))]
+    pub async fn import_subscribers_bulk(
+        &self,
+        subscribers: Vec<ImportSubscriberData>,
+        options: BulkImportOptions,
+    ) -> Result<BulkImportSummary> {
+        if subscribers.is_empty() {
+            return Err(Error::InvalidRequest("No subscribers provided".into()));
+        }
+        if options.chunk_size == 0 || options.chunk_size > MAX_IMPORT_BATCH_SIZE {
+            return Err(Error::InvalidBatchSize(
+                format!("Chunk size must be between 1 and {}", MAX_IMPORT_BATCH_SIZE)
+            ));
+        }
+        if options.concurrency == 0 {
+            return Err(Error::InvalidRequest("Concurrency must be positive".into()));
+        }
+
+        // Move the subscribers into the chunks rather than cloning them
+        let mut chunks = Vec::with_capacity(subscribers.len().div_ceil(options.chunk_size));
+        let mut subscribers = subscribers.into_iter();
+        loop {
+            let chunk: Vec<ImportSubscriberData> = subscribers.by_ref().take(options.chunk_size).collect();
+            if chunk.is_empty() {
+                break;
+            }
+            chunks.push(chunk);
+        }
+
+        let mut outcomes = stream::iter(chunks.into_iter().enumerate())
+            .map(|(index, chunk)| {
+                let options = &options;
+                async move {
+                    let result = self.import_chunk_with_retry(&chunk, options).await;
+                    (index, chunk, result)
+                }
+            })
+            .buffer_unordered(options.concurrency);
+
+        let mut summary = BulkImportSummary::default();
+        while let Some((index, chunk, result)) = outcomes.next().await {
+            match result {
+                Ok(mut outcome) => {
+                    for error in &mut outcome.errors {
+                        error.index = error.index.map(|i| i + index * options.chunk_size);
+                    }
+                    summary.outcome.merge(outcome);
+                }
+                Err(error) => {
+                    summary.outcome.failed += chunk.len() as u32;
+                    summary.failed_chunks.push(FailedChunk { index, subscribers: chunk, error });
+                }
             }
         }
+        summary.failed_chunks.sort_by_key(|chunk| chunk.index);
+
+        Ok(summary)
+    }
+
+    /// Sends one chunk, retrying it after retryable failures.
+    async fn import_chunk_with_retry(
+        &self,
+        chunk: &[ImportSubscriberData],
+        options: &BulkImportOptions,
+    ) -> Result<BatchOutcome> {
+        let mut attempt = 0;
+        loop {
+            match self.import_chunk(chunk).await {
+                Err(err) if err.is_retryable() && attempt < options.chunk_retries => {
+                    let delay = options.retry_delay.saturating_mul(2u32.saturating_pow(attempt));
+                    attempt += 1;
+                    warn!(attempt, ?delay, error = %err, "retrying subscriber import chunk");
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
 
+    /// Sends one request to `/batch/subscribers`.
+    #[instrument(skip_all, fields(count = subscribers.len()))]
+    async fn import_chunk(&self, subscribers: &[ImportSubscriberData]) -> Result<BatchOutcome> {
         let url = self.build_url("/batch/subscribers")?;
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_batch("/batch/subscribers", subscribers.len());
         let response = self.request(
-            self.http_client
-                .post(&url)
+            self.post(&url)
                 .json(&serde_json::json!({
                     "subscribers": subscribers
                 }))
         ).await?;
 
-        let import_response: ImportSubscriberResponse = response.json().await?;
-
-        if import_response.failed > 0 {
-            return Err(Error::UnexpectedResponse(
-                format!("Import partially failed: {} succeeded, {} failed",
-                        import_response.results, import_response.failed)
-            ));
-        }
-
-        Ok(())
+        let outcome: BatchOutcome = response.json()?;
+        Ok(outcome)
     }
 }
 
@@ -189,7 +352,7 @@ mod tests {
         custom_fields.insert("company".to_string(), json!("Acme Inc"));
 
         let subscriber = ImportSubscriberData {
-            email: "test@example.com".to_string(),
+            email: "test@example.com".parse().unwrap(),
             first_name: Some("John".to_string()),
             last_name: Some("Doe".to_string()),
             tags: Some("lead,mql".to_string()),
@@ -210,24 +373,35 @@ mod tests {
             .respond_with(ResponseTemplate::new(200)
                 .set_body_json(json!({
                     "results": 1,
-                    "failed": 1
+                    "failed": 1,
+                    "errors": [{
+                        "index": 1,
+                        "email": "other@example.com",
+                        "error": "email is blocked"
+                    }]
                 })))
             .mount(&mock_server)
             .await;
 
         let client = crate::test_utils::create_test_client(mock_server.uri());
 
-        let subscriber = ImportSubscriberData {
-            email: "test@example.com".to_string(),
+        let subscribers = ["test@example.com", "other@example.com"].iter().map(|email| ImportSubscriberData {
+            email: email.parse().unwrap(),
             first_name: None,
             last_name: None,
             tags: None,
             remove_tags: None,
             custom_fields: HashMap::new(),
-        };
-
-        let result = client.import_subscribers(vec![subscriber]).await;
-        assert!(matches!(result, Err(Error::UnexpectedResponse(_))));
+        }).collect();
+
+        let outcome = client.import_subscribers(subscribers).await.unwrap();
+        assert_eq!(outcome.succeeded, 1);
+        assert_eq!(outcome.failed, 1);
+        assert!(!outcome.is_complete_success());
+        assert_eq!(outcome.errors.len(), 1);
+        assert_eq!(outcome.errors[0].index, Some(1));
+        assert_eq!(outcome.errors[0].email.as_deref(), Some("other@example.com"));
+        assert_eq!(outcome.errors[0].message.as_deref(), Some("email is blocked"));
     }
 
     #[tokio::test]
@@ -238,16 +412,123 @@ mod tests {
         let result = client.create_subscriber("invalid-email").await;
         assert!(matches!(result, Err(Error::InvalidEmail(_))));
 
-        let subscriber = ImportSubscriberData {
-            email: "invalid-email".to_string(),
+        let result = client.find_subscriber("x y@example.com").await;
+        assert!(matches!(result, Err(Error::InvalidEmail(_))));
+    }
+
+    fn import_data(n: usize) -> ImportSubscriberData {
+        ImportSubscriberData {
+            email: format!("user{}@example.com", n).parse().unwrap(),
             first_name: None,
             last_name: None,
             tags: None,
             remove_tags: None,
             custom_fields: HashMap::new(),
-        };
+        }
+    }
 
-        let result = client.import_subscribers(vec![subscriber]).await;
-        assert!(matches!(result, Err(Error::InvalidEmail(_))));
+    #[tokio::test]
+    async fn test_import_subscribers_bulk() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/batch/subscribers"))
+            .respond_with(|request: &wiremock::Request| {
+                let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+                let count = body["subscribers"].as_array().unwrap().len();
+                ResponseTemplate::new(200).set_body_json(json!({
+                    "results": count - 1,
+                    "failed": 1,
+                    "errors": [{ "index": 0, "error": "email is blocked" }]
+                }))
+            })
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+
+        let client = crate::test_utils::create_test_client(mock_server.uri());
+        let subscribers = (0..25).map(import_data).collect();
+
+        let options = BulkImportOptions::new().chunk_size(10).concurrency(2);
+        let summary = client.import_subscribers_bulk(subscribers, options).await.unwrap();
+
+        assert_eq!(summary.outcome.succeeded, 22);
+        assert_eq!(summary.outcome.failed, 3);
+        assert!(summary.failed_chunks.is_empty());
+
+        let mut indexes: Vec<_> = summary.outcome.errors.iter().filter_map(|e| e.index).collect();
+        indexes.sort();
+        assert_eq!(indexes, vec![0, 10, 20]);
+    }
+
+    #[tokio::test]
+    async fn test_import_subscribers_bulk_retries_failed_chunks() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/batch/subscribers"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(4)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/batch/subscribers"))
+            .respond_with(ResponseTemplate::new(200)
+                .set_body_json(json!({
+                    "results": 5,
+                    "failed": 0
+                })))
+            .mount(&mock_server)
+            .await;
+
+        let client = crate::test_utils::create_test_client(mock_server.uri());
+        let subscribers = (0..5).map(import_data).collect();
+
+        // The client retries 3 times itself, so the chunk succeeds on its second attempt
+        let options = BulkImportOptions::new().retry_delay(Duration::from_millis(10));
+        let summary = client.import_subscribers_bulk(subscribers, options).await.unwrap();
+
+        assert_eq!(summary.outcome.succeeded, 5);
+        assert_eq!(summary.outcome.failed, 0);
+        assert!(summary.failed_chunks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_import_subscribers_bulk_reports_failed_chunks() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/batch/subscribers"))
+            .respond_with(ResponseTemplate::new(422))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let client = crate::test_utils::create_test_client(mock_server.uri());
+        let subscribers = (0..15).map(import_data).collect();
+
+        let options = BulkImportOptions::new().chunk_size(10);
+        let summary = client.import_subscribers_bulk(subscribers, options).await.unwrap();
+
+        assert_eq!(summary.outcome.succeeded, 0);
+        assert_eq!(summary.outcome.failed, 15);
+        assert_eq!(summary.failed_chunks.len(), 2);
+        assert_eq!(summary.failed_chunks[0].index, 0);
+        assert_eq!(summary.failed_chunks[1].subscribers.len(), 5);
+        assert_eq!(summary.failed_chunks[1].error.status(), Some(422));
+    }
+
+    #[tokio::test]
+    async fn test_import_subscribers_bulk_validation() {
+        let mock_server = MockServer::start().await;
+        let client = crate::test_utils::create_test_client(mock_server.uri());
+
+        let result = client.import_subscribers_bulk(vec![], BulkImportOptions::new()).await;
+        assert!(matches!(result, Err(Error::InvalidRequest(_))));
+
+        let options = BulkImportOptions::new().chunk_size(1001);
+        let result = client.import_subscribers_bulk(vec![import_data(0)], options).await;
+        assert!(matches!(result, Err(Error::InvalidBatchSize(_))));
     }
 }
\ No newline at end of file
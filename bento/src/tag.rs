@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use tracing::instrument;
 
 /// Tag data returned from the API
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TagData {
     /// Tag ID
     pub id: String,
@@ -15,7 +15,7 @@ pub struct TagData {
 }
 
 /// Tag attributes
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TagAttributes {
     /// Tag name
     pub name: String,
@@ -29,11 +29,11 @@ pub struct TagAttributes {
 
 impl Client {
     /// Get all tags
-    #[instrument(skip(self))]
+    #[instrument(skip_all)]
     pub async fn get_tags(&self) -> Result<Vec<TagData>> {
         let url = self.build_url("/fetch/tags")?;
         let response = self.request(
-            self.http_client.get(&url)
+            self.get(&url)
         ).await?;
 
         #[derive(Deserialize)]
@@ -41,12 +41,12 @@ impl Client {
             data: Vec<TagData>,
         }
 
-        let tag_response: TagResponse = response.json().await?;
+        let tag_response: TagResponse = response.json()?;
         Ok(tag_response.data)
     }
 
     /// Create a new tag
-    #[instrument(skip(self))]
+    #[instrument(skip_all, fields(name = %name))]
     pub async fn create_tag(&self, name: &str) -> Result<TagData> {
         if name.is_empty() {
             return Err(Error::InvalidRequest("Tag name is required".into()));
@@ -54,8 +54,7 @@ impl Client {
 
         let url = self.build_url("/fetch/tags")?;
         let response = self.request(
-            self.http_client
-                .post(&url)
+            self.post(&url)
                 .json(&serde_json::json!({
                     "tag": {
                         "name": name
@@ -68,7 +67,7 @@ impl Client {
             data: TagData,
         }
 
-        let tag_response: TagResponse = response.json().await?;
+        let tag_response: TagResponse = response.json()?;
         Ok(tag_response.data)
     }
 }
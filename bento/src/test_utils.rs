@@ -1,4 +1,4 @@
-use crate::{Client, Config};
+use crate::{Client, Config, RetryPolicy};
 use wiremock::{MockServer};
 use std::time::Duration;
 
@@ -9,10 +9,14 @@ use std::time::Duration;
     pub fn create_test_client(base_url: String) -> Client {
         let config = Config {
             publishable_key: "test_pub_key".into(),
-            secret_key: "test_secret_key".into(),
+            secret_key: secrecy::SecretString::new("test_secret_key".into()),
             site_uuid: "test_site_uuid".into(),
             timeout: Duration::from_secs(30),
             base_url,
+            retry_policy: RetryPolicy::default(),
+            rate_limit: None,
+            redaction: crate::Redaction::default(),
+            interceptors: Default::default(),
         };
 
         Client::new(config).expect("Failed to create test client")
@@ -41,7 +45,7 @@ mod tests {
             .mount(&mock_server)
             .await;
 
-        let request = client.http_client.get(format!("{}/test", mock_server.uri()));
+        let request = client.get(&format!("{}/test", mock_server.uri()));
         let response = client.request(request).await;
 
         assert!(response.is_ok());
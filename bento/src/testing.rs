@@ -0,0 +1,785 @@
+//! In-memory fake of the Bento API for tests.
+//!
+//! [`FakeBento`] implements [`Transport`] and answers every endpoint the
+//! [`Client`] calls from in-memory state, so downstream crates can exercise
+//! their Bento integration without a network or hand-written HTTP stubs, and
+//! then assert on the resulting state rather than on request bodies.
+//!
+//! Enable it with the `testing` feature:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! bento = { version = "0.1", features = ["testing"] }
+//! ```
+//!
+//! # Example
+//! ```
+//! use bento::Command;
+//! use bento::testing::FakeBento;
+//!
+//! # tokio_test::block_on(async {
+//! let bento = FakeBento::new();
+//! let client = bento.client();
+//!
+//! client.subscriber_command(vec![
+//!     Command::add_tag("user@example.com".parse()?, "customer"),
+//! ]).await?;
+//!
+//! assert!(bento.has_tag("user@example.com", "customer"));
+//! # Ok::<(), bento::Error>(())
+//! # }).unwrap();
+//! ```
+
+use crate::field::{FieldAttributes, FieldData};
+use crate::tag::{TagAttributes, TagData};
+use crate::{
+    BatchItemError, BroadcastData, Client, Command, ConfigBuilder, EmailAddress, EmailData, EventData,
+    HttpRequest, HttpResponse, ImportSubscriberData, Method, Result, RetryPolicy, SubscriberAttributes,
+    SubscriberData, Transport,
+};
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+/// Base URL used by clients created with [`FakeBento::client`]
+pub const FAKE_BASE_URL: &str = "https://bento.test/api/v1";
+
+/// Path prefix stripped from request paths before routing
+const API_PREFIX: &str = "/api/v1";
+
+/// Error message and status returned for a rejected request
+type Rejection = (u16, String);
+
+/// A stateful, in-memory fake of the Bento API
+///
+/// Cloning a `FakeBento` returns another handle to the same state, so one handle
+/// can be given to a [`Client`] while the test keeps another for assertions.
+///
+/// Behaviour follows the real API closely enough for integration tests:
+/// * events, imports and most commands create subscribers that don't exist yet
+/// * tags and fields applied to subscribers are added to the site's tag and field lists
+/// * `remove_tag`, `remove_field` and `change_email` report a per-item error for
+///   unknown subscribers
+/// * stats and experimental endpoints return fixed responses unless stubbed
+///
+/// Requests for unknown endpoints get a 404.
+#[derive(Debug, Clone, Default)]
+pub struct FakeBento {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    subscribers: BTreeMap<String, FakeSubscriber>,
+    tags: Vec<TagData>,
+    fields: Vec<FieldData>,
+    events: Vec<EventData>,
+    commands: Vec<Command>,
+    emails: Vec<EmailData>,
+    broadcasts: Vec<BroadcastData>,
+    requests: Vec<HttpRequest>,
+    stubs: HashMap<(Method, String), HttpResponse>,
+    queued: VecDeque<HttpResponse>,
+    next_id: u64,
+}
+
+#[derive(Debug, Clone)]
+struct FakeSubscriber {
+    id: u64,
+    email: String,
+    fields: HashMap<String, Value>,
+    tags: BTreeSet<String>,
+    unsubscribed_at: Option<OffsetDateTime>,
+}
+
+impl FakeBento {
+    /// Create an empty fake
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a client that sends its requests to this fake
+    ///
+    /// Retries are disabled so failures queued with [`fail_next`](Self::fail_next)
+    /// reach the caller directly. To test retry behaviour, pass a clone of the fake to
+    /// [`Client::with_transport`] with your own configuration.
+    pub fn client(&self) -> Client {
+        let config = ConfigBuilder::new()
+            .publishable_key("fake_publishable_key")
+            .secret_key("fake_secret_key")
+            .site_uuid("fake_site_uuid")
+            .base_url(FAKE_BASE_URL)
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .expect("fake configuration is valid");
+
+        Client::with_transport(config, self.clone())
+    }
+
+    /// Add a subscriber as if it had been imported
+    pub fn add_subscriber(&self, data: ImportSubscriberData) {
+        self.lock().import(data);
+    }
+
+    /// Return `response` for every `method` request to `path`, instead of the fake's own handling
+    ///
+    /// `path` is relative to the API root, e.g. `/stats/report`.
+    pub fn stub(&self, method: Method, path: &str, response: HttpResponse) {
+        self.lock().stubs.insert((method, path.to_string()), response);
+    }
+
+    /// Answer the next request with an error status, without touching any state
+    pub fn fail_next(&self, status: u16) {
+        let response = HttpResponse::json_body(status, &json!({ "error": "Injected failure" }))
+            .expect("error body is serializable");
+        self.lock().queued.push_back(response);
+    }
+
+    /// Answer the next request with `response`, without touching any state
+    pub fn respond_next(&self, response: HttpResponse) {
+        self.lock().queued.push_back(response);
+    }
+
+    /// Get a subscriber as the API would return it
+    pub fn subscriber(&self, email: &str) -> Option<SubscriberData> {
+        let state = self.lock();
+        state.subscribers.get(&normalize(email)).map(|s| state.subscriber_data(s))
+    }
+
+    /// Get the emails of all subscribers, in sorted order
+    pub fn subscriber_emails(&self) -> Vec<String> {
+        self.lock().subscribers.values().map(|s| s.email.clone()).collect()
+    }
+
+    /// Returns true if the subscriber exists and has the tag
+    pub fn has_tag(&self, email: &str, tag: &str) -> bool {
+        self.lock().subscribers.get(&normalize(email)).is_some_and(|s| s.tags.contains(tag))
+    }
+
+    /// Get the names of the tags on a subscriber, in sorted order
+    pub fn subscriber_tags(&self, email: &str) -> Vec<String> {
+        self.lock().subscribers.get(&normalize(email))
+            .map(|s| s.tags.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Get the value of a field on a subscriber
+    pub fn field(&self, email: &str, key: &str) -> Option<Value> {
+        self.lock().subscribers.get(&normalize(email)).and_then(|s| s.fields.get(key).cloned())
+    }
+
+    /// Returns true if the subscriber exists and is unsubscribed
+    pub fn is_unsubscribed(&self, email: &str) -> bool {
+        self.lock().subscribers.get(&normalize(email)).is_some_and(|s| s.unsubscribed_at.is_some())
+    }
+
+    /// Get every tracked event, in the order received
+    pub fn events(&self) -> Vec<EventData> {
+        self.lock().events.clone()
+    }
+
+    /// Get the events tracked for one subscriber, in the order received
+    pub fn events_for(&self, email: &str) -> Vec<EventData> {
+        let email = normalize(email);
+        self.lock().events.iter().filter(|e| normalize(e.email.as_str()) == email).cloned().collect()
+    }
+
+    /// Get every command applied, including ones that failed, in the order received
+    pub fn commands(&self) -> Vec<Command> {
+        self.lock().commands.clone()
+    }
+
+    /// Get every email sent, in the order received
+    pub fn emails(&self) -> Vec<EmailData> {
+        self.lock().emails.clone()
+    }
+
+    /// Get every broadcast created, in the order received
+    pub fn broadcasts(&self) -> Vec<BroadcastData> {
+        self.lock().broadcasts.clone()
+    }
+
+    /// Get the site's tags
+    pub fn tags(&self) -> Vec<TagData> {
+        self.lock().tags.clone()
+    }
+
+    /// Get the site's custom fields
+    pub fn fields(&self) -> Vec<FieldData> {
+        self.lock().fields.clone()
+    }
+
+    /// Get every request received, including stubbed and failed ones
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.lock().requests.clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn handle(&self, request: HttpRequest) -> HttpResponse {
+        let mut state = self.lock();
+        state.requests.push(request.clone());
+
+        if let Some(response) = state.queued.pop_front() {
+            return response;
+        }
+
+        let path = request.path();
+        let path = path.strip_prefix(API_PREFIX).unwrap_or(path).to_string();
+        if let Some(response) = state.stubs.get(&(request.method, path.clone())) {
+            return response.clone();
+        }
+
+        let result = state.route(request.method, &path, &request);
+        let (status, body) = match result {
+            Ok(body) => (200, body),
+            Err((status, message)) => (status, json!({ "error": message })),
+        };
+        HttpResponse::json_body(status, &body).expect("response body is serializable")
+    }
+}
+
+#[async_trait]
+impl Transport for FakeBento {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        Ok(self.handle(request))
+    }
+}
+
+impl State {
+    fn route(&mut self, method: Method, path: &str, request: &HttpRequest) -> std::result::Result<Value, Rejection> {
+        match (method, path) {
+            (Method::Get, "/fetch/subscribers") => self.find_subscriber(request),
+            (Method::Post, "/fetch/subscribers") => self.create_subscriber(request),
+            (Method::Post, "/batch/subscribers") => self.import_subscribers(request),
+            (Method::Post, "/batch/events") => self.track_events(request),
+            (Method::Post, "/fetch/commands") => self.apply_commands(request),
+            (Method::Post, "/batch/emails") => self.send_emails(request),
+            (Method::Get, "/fetch/tags") => Ok(json!({ "data": self.tags })),
+            (Method::Post, "/fetch/tags") => self.create_tag(request),
+            (Method::Get, "/fetch/fields") => Ok(json!({ "data": self.fields })),
+            (Method::Post, "/fetch/fields") => self.create_field(request),
+            (Method::Get, "/fetch/broadcasts") => Ok(json!({ "broadcasts": self.broadcasts })),
+            (Method::Post, "/batch/broadcasts") => self.create_broadcasts(request),
+            (Method::Get, "/stats/site") => Ok(self.site_stats()),
+            (Method::Get, "/stats/segment") => Ok(json!({
+                "user_count": 0,
+                "subscriber_count": 0,
+                "unsubscriber_count": 0,
+            })),
+            (Method::Get, "/stats/report") => Ok(json!({
+                "report_data": {
+                    "data": {},
+                    "chart_style": "count",
+                    "report_type": "Reporting::Reports::VisitorCountReport",
+                    "report_name": query_param(request, "report_id"),
+                }
+            })),
+            (Method::Get, "/experimental/blacklist.json") => {
+                let query = query_param(request, "domain").or_else(|| query_param(request, "ip"));
+                Ok(json!({ "query": query, "description": "Not listed", "results": {} }))
+            }
+            (Method::Post, "/experimental/validation") => {
+                #[derive(Deserialize)]
+                struct Body {
+                    email: String,
+                }
+                let body: Body = parse_body(request)?;
+                Ok(match EmailAddress::parse(&body.email) {
+                    Ok(_) => json!({ "valid": true, "reasons": [] }),
+                    Err(_) => json!({ "valid": false, "reasons": ["invalid syntax"] }),
+                })
+            }
+            (Method::Post, "/experimental/content_moderation") => {
+                Ok(json!({ "flagged": false, "categories": {}, "category_scores": {} }))
+            }
+            (Method::Post, "/experimental/gender") => Ok(json!({ "gender": "unknown", "confidence": null })),
+            (Method::Get, "/experimental/geolocation") => Ok(json!({ "ip": query_param(request, "ip") })),
+            _ => Err((404, "Not found".into())),
+        }
+    }
+
+    fn find_subscriber(&self, request: &HttpRequest) -> std::result::Result<Value, Rejection> {
+        let email = query_param(request, "email").ok_or((400, "email is required".to_string()))?;
+        let subscriber = self.subscribers.get(&normalize(&email))
+            .ok_or((404, "Subscriber not found".to_string()))?;
+
+        Ok(json!({ "data": self.subscriber_data(subscriber) }))
+    }
+
+    fn create_subscriber(&mut self, request: &HttpRequest) -> std::result::Result<Value, Rejection> {
+        #[derive(Deserialize)]
+        struct Body {
+            subscriber: crate::CreateSubscriberData,
+        }
+        let body: Body = parse_body(request)?;
+        let email = body.subscriber.email.as_str();
+        self.upsert(email);
+
+        Ok(json!({ "data": self.subscriber_data(&self.subscribers[&normalize(email)]) }))
+    }
+
+    fn import_subscribers(&mut self, request: &HttpRequest) -> std::result::Result<Value, Rejection> {
+        #[derive(Deserialize)]
+        struct Body {
+            subscribers: Vec<ImportSubscriberData>,
+        }
+        let body: Body = parse_body(request)?;
+        let count = body.subscribers.len();
+        for subscriber in body.subscribers {
+            self.import(subscriber);
+        }
+
+        Ok(batch_response(count, Vec::new()))
+    }
+
+    fn track_events(&mut self, request: &HttpRequest) -> std::result::Result<Value, Rejection> {
+        #[derive(Deserialize)]
+        struct Body {
+            events: Vec<EventData>,
+        }
+        let body: Body = parse_body(request)?;
+        let count = body.events.len();
+        for event in body.events {
+            let fields = event.fields.clone().unwrap_or_default();
+            self.upsert(event.email.as_str()).fields.extend(fields.clone());
+            for key in fields.keys() {
+                self.ensure_field(key);
+            }
+            self.events.push(event);
+        }
+
+        Ok(batch_response(count, Vec::new()))
+    }
+
+    fn apply_commands(&mut self, request: &HttpRequest) -> std::result::Result<Value, Rejection> {
+        #[derive(Deserialize)]
+        struct Body {
+            command: Vec<Command>,
+        }
+        let body: Body = parse_body(request)?;
+        let count = body.command.len();
+        let mut errors = Vec::new();
+        for (index, command) in body.command.into_iter().enumerate() {
+            if let Err(message) = self.apply(&command) {
+                errors.push(BatchItemError {
+                    index: Some(index),
+                    email: Some(command.email().to_string()),
+                    message: Some(message),
+                    ..BatchItemError::default()
+                });
+            }
+            self.commands.push(command);
+        }
+
+        Ok(batch_response(count, errors))
+    }
+
+    fn send_emails(&mut self, request: &HttpRequest) -> std::result::Result<Value, Rejection> {
+        #[derive(Deserialize)]
+        struct Body {
+            emails: Vec<EmailData>,
+        }
+        let body: Body = parse_body(request)?;
+        let count = body.emails.len();
+        self.emails.extend(body.emails);
+
+        Ok(batch_response(count, Vec::new()))
+    }
+
+    fn create_tag(&mut self, request: &HttpRequest) -> std::result::Result<Value, Rejection> {
+        #[derive(Deserialize)]
+        struct Tag {
+            name: String,
+        }
+        #[derive(Deserialize)]
+        struct Body {
+            tag: Tag,
+        }
+        let body: Body = parse_body(request)?;
+        if body.tag.name.is_empty() {
+            return Err((422, "Tag name can't be blank".into()));
+        }
+
+        Ok(json!({ "data": self.ensure_tag(&body.tag.name) }))
+    }
+
+    fn create_field(&mut self, request: &HttpRequest) -> std::result::Result<Value, Rejection> {
+        #[derive(Deserialize)]
+        struct Field {
+            key: String,
+        }
+        #[derive(Deserialize)]
+        struct Body {
+            field: Field,
+        }
+        let body: Body = parse_body(request)?;
+        if body.field.key.is_empty() {
+            return Err((422, "Field key can't be blank".into()));
+        }
+
+        Ok(json!({ "data": self.ensure_field(&body.field.key) }))
+    }
+
+    fn create_broadcasts(&mut self, request: &HttpRequest) -> std::result::Result<Value, Rejection> {
+        #[derive(Deserialize)]
+        struct Body {
+            broadcasts: Vec<BroadcastData>,
+        }
+        let body: Body = parse_body(request)?;
+        let count = body.broadcasts.len();
+        self.broadcasts.extend(body.broadcasts);
+
+        Ok(batch_response(count, Vec::new()))
+    }
+
+    fn site_stats(&self) -> Value {
+        let unsubscribed = self.subscribers.values().filter(|s| s.unsubscribed_at.is_some()).count();
+        json!({
+            "user_count": self.subscribers.len(),
+            "subscriber_count": self.subscribers.len() - unsubscribed,
+            "unsubscriber_count": unsubscribed,
+        })
+    }
+
+    /// Applies one command, returning the per-item error message on failure.
+    fn apply(&mut self, command: &Command) -> std::result::Result<(), String> {
+        match command {
+            Command::AddTag { email, tag } | Command::AddTagViaEvent { email, tag } => {
+                self.upsert(email.as_str()).tags.insert(tag.clone());
+                self.ensure_tag(tag);
+            }
+            Command::RemoveTag { email, tag } => {
+                self.existing(email)?.tags.remove(tag);
+            }
+            Command::AddField { email, key, value } => {
+                self.upsert(email.as_str()).fields.insert(key.clone(), value.clone());
+                self.ensure_field(key);
+            }
+            Command::RemoveField { email, key } => {
+                self.existing(email)?.fields.remove(key);
+            }
+            Command::Subscribe { email } => {
+                self.upsert(email.as_str()).unsubscribed_at = None;
+            }
+            Command::Unsubscribe { email } => {
+                let subscriber = self.upsert(email.as_str());
+                subscriber.unsubscribed_at.get_or_insert_with(OffsetDateTime::now_utc);
+            }
+            Command::ChangeEmail { email, new_email } => {
+                let new_key = normalize(new_email.as_str());
+                if self.subscribers.contains_key(&new_key) {
+                    return Err(format!("Subscriber {} already exists", new_email));
+                }
+                let mut subscriber = self.subscribers.remove(&normalize(email.as_str()))
+                    .ok_or_else(|| "Subscriber not found".to_string())?;
+                subscriber.email = new_email.to_string();
+                self.subscribers.insert(new_key, subscriber);
+            }
+        }
+        Ok(())
+    }
+
+    fn import(&mut self, data: ImportSubscriberData) {
+        let mut fields = data.custom_fields;
+        if let Some(first_name) = data.first_name {
+            fields.insert("first_name".into(), Value::String(first_name));
+        }
+        if let Some(last_name) = data.last_name {
+            fields.insert("last_name".into(), Value::String(last_name));
+        }
+        let add = split_tags(data.tags.as_deref());
+        let remove = split_tags(data.remove_tags.as_deref());
+
+        let subscriber = self.upsert(data.email.as_str());
+        subscriber.fields.extend(fields.clone());
+        subscriber.tags.extend(add.iter().cloned());
+        for tag in &remove {
+            subscriber.tags.remove(tag);
+        }
+
+        for key in fields.keys() {
+            self.ensure_field(key);
+        }
+        for tag in &add {
+            self.ensure_tag(tag);
+        }
+    }
+
+    fn upsert(&mut self, email: &str) -> &mut FakeSubscriber {
+        let key = normalize(email);
+        if !self.subscribers.contains_key(&key) {
+            let id = self.next_id();
+            self.subscribers.insert(key.clone(), FakeSubscriber {
+                id,
+                email: email.to_string(),
+                fields: HashMap::new(),
+                tags: BTreeSet::new(),
+                unsubscribed_at: None,
+            });
+        }
+        self.subscribers.get_mut(&key).expect("subscriber was just inserted")
+    }
+
+    fn existing(&mut self, email: &EmailAddress) -> std::result::Result<&mut FakeSubscriber, String> {
+        self.subscribers.get_mut(&normalize(email.as_str())).ok_or_else(|| "Subscriber not found".to_string())
+    }
+
+    fn ensure_tag(&mut self, name: &str) -> TagData {
+        if let Some(tag) = self.tags.iter().find(|t| t.attributes.name == name) {
+            return tag.clone();
+        }
+
+        let tag = TagData {
+            id: self.next_id().to_string(),
+            data_type: "tags".into(),
+            attributes: TagAttributes {
+                name: name.to_string(),
+                created_at: now_rfc3339(),
+                discarded_at: None,
+                site_id: 1,
+            },
+        };
+        self.tags.push(tag.clone());
+        tag
+    }
+
+    fn ensure_field(&mut self, key: &str) -> FieldData {
+        if let Some(field) = self.fields.iter().find(|f| f.attributes.key == key) {
+            return field.clone();
+        }
+
+        let field = FieldData {
+            id: self.next_id().to_string(),
+            data_type: "visitors-fields".into(),
+            attributes: FieldAttributes {
+                name: display_name(key),
+                key: key.to_string(),
+                whitelisted: None,
+                created_at: Some(OffsetDateTime::now_utc()),
+            },
+        };
+        self.fields.push(field.clone());
+        field
+    }
+
+    fn subscriber_data(&self, subscriber: &FakeSubscriber) -> SubscriberData {
+        let cached_tag_ids = subscriber.tags.iter()
+            .filter_map(|name| self.tags.iter().find(|t| &t.attributes.name == name))
+            .map(|tag| tag.id.clone())
+            .collect();
+
+        SubscriberData {
+            id: subscriber.id.to_string(),
+            data_type: "visitors".into(),
+            attributes: SubscriberAttributes {
+                uuid: format!("00000000-0000-4000-8000-{:012x}", subscriber.id),
+                email: subscriber.email.clone(),
+                fields: subscriber.fields.clone(),
+                cached_tag_ids,
+                unsubscribed_at: subscriber.unsubscribed_at,
+            },
+        }
+    }
+
+    fn next_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+}
+
+/// Emails are matched case-insensitively, like the API does.
+fn normalize(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+fn split_tags(tags: Option<&str>) -> Vec<String> {
+    tags.unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Turns a field key such as `first_name` into a display name such as `First Name`.
+fn display_name(key: &str) -> String {
+    key.split(['_', '-', ' '])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |c| c.to_uppercase().chain(chars).collect())
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn now_rfc3339() -> String {
+    OffsetDateTime::now_utc().format(&Rfc3339).unwrap_or_default()
+}
+
+fn query_param(request: &HttpRequest, name: &str) -> Option<String> {
+    url::Url::parse(&request.url).ok()?
+        .query_pairs()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.into_owned())
+}
+
+fn parse_body<T: DeserializeOwned>(request: &HttpRequest) -> std::result::Result<T, Rejection> {
+    let body = request.body.as_deref().unwrap_or_default();
+    serde_json::from_slice(body).map_err(|e| (400, format!("Invalid request body: {}", e)))
+}
+
+fn batch_response(count: usize, errors: Vec<BatchItemError>) -> Value {
+    json!({
+        "results": count - errors.len(),
+        "failed": errors.len(),
+        "errors": errors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::email::EmailBatch;
+    use crate::Error;
+
+    fn email(address: &str) -> EmailAddress {
+        address.parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_subscribers() {
+        let bento = FakeBento::new();
+        let client = bento.client();
+
+        let created = client.create_subscriber("new@example.com").await.unwrap();
+        let found = client.find_subscriber("new@example.com").await.unwrap();
+        assert_eq!(created.id, found.id);
+        assert_eq!(found.attributes.email, "new@example.com");
+
+        let result = client.find_subscriber("missing@example.com").await;
+        assert!(matches!(result, Err(Error::Api { status: 404, .. })));
+
+        let mut custom_fields = HashMap::new();
+        custom_fields.insert("plan".to_string(), json!("pro"));
+        let outcome = client.import_subscribers(vec![ImportSubscriberData {
+            email: email("import@example.com"),
+            first_name: Some("Jane".into()),
+            last_name: None,
+            tags: Some("lead, customer".into()),
+            remove_tags: None,
+            custom_fields,
+        }]).await.unwrap();
+
+        assert_eq!(outcome.succeeded, 1);
+        assert_eq!(bento.subscriber_tags("import@example.com"), vec!["customer", "lead"]);
+        assert_eq!(bento.field("import@example.com", "first_name"), Some(json!("Jane")));
+        assert_eq!(bento.field("import@example.com", "plan"), Some(json!("pro")));
+        assert_eq!(bento.subscriber_emails(), vec!["import@example.com", "new@example.com"]);
+
+        let subscriber = bento.subscriber("import@example.com").unwrap();
+        assert_eq!(subscriber.attributes.cached_tag_ids.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_commands() {
+        let bento = FakeBento::new();
+        let client = bento.client();
+        let user = email("user@example.com");
+
+        let outcome = client.subscriber_command(vec![
+            Command::add_tag(user.clone(), "vip"),
+            Command::add_field(user.clone(), "score", 42),
+            Command::unsubscribe(user.clone()),
+            Command::remove_tag(email("missing@example.com"), "vip"),
+        ]).await.unwrap();
+
+        assert_eq!(outcome.succeeded, 3);
+        assert_eq!(outcome.failed, 1);
+        assert_eq!(outcome.errors[0].index, Some(3));
+        assert!(bento.has_tag("user@example.com", "vip"));
+        assert_eq!(bento.field("user@example.com", "score"), Some(json!(42)));
+        assert!(bento.is_unsubscribed("user@example.com"));
+        assert_eq!(bento.commands().len(), 4);
+
+        client.subscriber_command(vec![
+            Command::change_email(user, email("renamed@example.com")),
+        ]).await.unwrap();
+
+        assert!(bento.subscriber("user@example.com").is_none());
+        assert!(bento.has_tag("renamed@example.com", "vip"));
+
+        let tags = client.get_tags().await.unwrap();
+        assert_eq!(tags[0].attributes.name, "vip");
+        let fields = client.get_fields().await.unwrap();
+        assert_eq!(fields[0].attributes.name, "Score");
+    }
+
+    #[tokio::test]
+    async fn test_events_and_emails() {
+        let bento = FakeBento::new();
+        let client = bento.client();
+
+        let mut fields = HashMap::new();
+        fields.insert("first_name".to_string(), json!("John"));
+        client.track_events(vec![EventData {
+            event_type: "$completed_onboarding".into(),
+            email: email("user@example.com"),
+            fields: Some(fields),
+            details: None,
+        }]).await.unwrap();
+
+        assert_eq!(bento.events_for("USER@example.com").len(), 1);
+        assert_eq!(bento.field("user@example.com", "first_name"), Some(json!("John")));
+
+        client.send_emails(EmailBatch::new(vec![EmailData {
+            to: email("user@example.com"),
+            from: email("sender@example.com"),
+            subject: "Hello".into(),
+            html_body: "<p>Hi</p>".into(),
+            transactional: true,
+            personalizations: None,
+        }]).unwrap()).await.unwrap();
+
+        assert_eq!(bento.emails()[0].subject, "Hello");
+
+        let stats = client.get_site_stats().await.unwrap();
+        assert_eq!(stats.user_count, 1);
+        assert_eq!(stats.subscriber_count, 1);
+
+        let stats = client.get_segment_stats("segment_123").await.unwrap();
+        assert_eq!(stats.user_count, 0);
+
+        let stats = client.get_report_stats("report_123").await.unwrap();
+        assert_eq!(stats.report_data.report_name.as_deref(), Some("report_123"));
+    }
+
+    #[tokio::test]
+    async fn test_stubs_and_failures() {
+        let bento = FakeBento::new();
+        let client = bento.client();
+
+        bento.stub(
+            Method::Get,
+            "/stats/report",
+            HttpResponse::json_body(200, &json!({ "report_data": { "data": { "opens": 3 } } })).unwrap(),
+        );
+        let stats = client.get_report_stats("report_123").await.unwrap();
+        assert_eq!(stats.report_data.data["opens"], 3);
+
+        bento.fail_next(503);
+        let result = client.get_tags().await;
+        assert!(matches!(result, Err(Error::Api { status: 503, .. })));
+
+        assert!(client.get_tags().await.is_ok());
+        assert_eq!(bento.requests().len(), 3);
+    }
+}
\ No newline at end of file
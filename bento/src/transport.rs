@@ -0,0 +1,356 @@
+//! HTTP transport abstraction used by the client.
+//!
+//! The [`Client`](crate::Client) builds transport-agnostic [`HttpRequest`] values and hands
+//! them to a [`Transport`]. [`ReqwestTransport`] is used by default; a custom transport can
+//! be supplied with [`Client::with_transport`](crate::Client::with_transport) to use another
+//! HTTP stack, custom TLS, or an in-memory fake in tests.
+
+use crate::{Error, Result};
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt;
+use std::time::Duration;
+
+/// HTTP method of a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Method {
+    /// GET request
+    Get,
+    /// POST request
+    Post,
+    /// PUT request
+    Put,
+    /// PATCH request
+    Patch,
+    /// DELETE request
+    Delete,
+}
+
+impl Method {
+    /// Returns the method name as used on the wire
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+            Method::Put => "PUT",
+            Method::Patch => "PATCH",
+            Method::Delete => "DELETE",
+        }
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Headers whose values are redacted from `Debug` output
+const SENSITIVE_HEADERS: [&str; 2] = ["authorization", "proxy-authorization"];
+
+/// An outgoing HTTP request
+///
+/// The `Debug` output redacts credential headers such as `Authorization`, so
+/// requests can be logged safely.
+#[derive(Clone)]
+pub struct HttpRequest {
+    /// HTTP method
+    pub method: Method,
+    /// Absolute URL including the query string
+    pub url: String,
+    /// Request headers as name/value pairs
+    pub headers: Vec<(String, String)>,
+    /// Request body, if any
+    pub body: Option<Vec<u8>>,
+}
+
+impl HttpRequest {
+    /// Create a request without headers or body
+    pub fn new(method: Method, url: impl Into<String>) -> Self {
+        Self {
+            method,
+            url: url.into(),
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    /// Get the first value of a header, matching the name case-insensitively
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+
+    /// Get the path component of the URL
+    pub fn path(&self) -> &str {
+        let without_scheme = self.url.split_once("://").map_or(self.url.as_str(), |(_, rest)| rest);
+        let path = without_scheme.find('/').map_or("", |i| &without_scheme[i..]);
+        path.split(['?', '#']).next().unwrap_or("")
+    }
+}
+
+impl fmt::Debug for HttpRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let headers: Vec<(&str, &str)> = self.headers.iter()
+            .map(|(name, value)| {
+                let value = if is_sensitive_header(name) { "[REDACTED]" } else { value.as_str() };
+                (name.as_str(), value)
+            })
+            .collect();
+
+        f.debug_struct("HttpRequest")
+            .field("method", &self.method)
+            .field("url", &self.url)
+            .field("headers", &headers)
+            .field("body", &self.body)
+            .finish()
+    }
+}
+
+/// A response received from a transport
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// Response headers as name/value pairs
+    pub headers: Vec<(String, String)>,
+    /// Response body
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Create a response without headers
+    pub fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    /// Create a response with a JSON body
+    ///
+    /// # Errors
+    /// Returns an error if the value cannot be serialized.
+    pub fn json_body<T: Serialize + ?Sized>(status: u16, value: &T) -> Result<Self> {
+        let body = serde_json::to_vec(value)?;
+        Ok(Self::new(status, body).with_header("Content-Type", "application/json"))
+    }
+
+    /// Add a header to the response
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Get the first value of a header, matching the name case-insensitively
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+
+    /// Returns true if the status is in the 2xx range
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Deserialize the body as JSON
+    ///
+    /// # Errors
+    /// Returns an error if the body is not valid JSON for `T`.
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T> {
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+
+    /// Get the body as text, replacing invalid UTF-8
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+fn is_sensitive_header(name: &str) -> bool {
+    SENSITIVE_HEADERS.iter().any(|sensitive| sensitive.eq_ignore_ascii_case(name))
+}
+
+fn find_header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers.iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+/// Sends HTTP requests on behalf of the client
+///
+/// Implementations only need to perform the exchange; authentication, retries and
+/// status handling are done by the [`Client`](crate::Client). Transports should return
+/// any response they receive, including non-2xx statuses, and report failures to
+/// reach the server as [`Error::Connection`] or [`Error::Timeout`] so they are retried.
+#[async_trait]
+pub trait Transport: Send + Sync {
+    /// Send a request and return the response
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse>;
+}
+
+/// Default transport backed by [`reqwest`]
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Create a transport with the given request timeout
+    ///
+    /// # Errors
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(timeout: Duration) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| Error::InvalidConfig(e.to_string()))?;
+
+        Ok(Self { client })
+    }
+
+    /// Create a transport from an existing reqwest client
+    pub fn from_client(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl Transport for ReqwestTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let method = reqwest::Method::from_bytes(request.method.as_str().as_bytes())
+            .map_err(|e| Error::InvalidRequest(e.to_string()))?;
+
+        let mut builder = self.client.request(method, &request.url);
+        for (name, value) in &request.headers {
+            let mut value = reqwest::header::HeaderValue::from_str(value)
+                .map_err(|e| Error::InvalidRequest(e.to_string()))?;
+            value.set_sensitive(is_sensitive_header(name));
+            builder = builder.header(name, value);
+        }
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+
+        let response = builder.send().await?;
+        let status = response.status().as_u16();
+        let headers = response.headers()
+            .iter()
+            .filter_map(|(name, value)| {
+                value.to_str().ok().map(|value| (name.to_string(), value.to_string()))
+            })
+            .collect();
+        let body = response.bytes().await?.to_vec();
+
+        Ok(HttpResponse { status, headers, body })
+    }
+}
+
+/// Builder for requests made by the client
+///
+/// Mirrors the subset of `reqwest::RequestBuilder` used by the endpoint modules.
+/// Errors are deferred until the request is sent.
+#[derive(Debug)]
+pub(crate) struct RequestBuilder {
+    request: Result<HttpRequest>,
+}
+
+impl RequestBuilder {
+    pub(crate) fn new(method: Method, url: &str) -> Self {
+        Self {
+            request: Ok(HttpRequest::new(method, url)),
+        }
+    }
+
+    /// Append query parameters to the URL
+    pub(crate) fn query<K: AsRef<str>, V: AsRef<str>>(mut self, params: &[(K, V)]) -> Self {
+        if let Ok(request) = &mut self.request {
+            match url::Url::parse(&request.url) {
+                Ok(mut url) => {
+                    url.query_pairs_mut()
+                        .extend_pairs(params.iter().map(|(k, v)| (k.as_ref(), v.as_ref())));
+                    request.url = url.into();
+                }
+                Err(e) => self.request = Err(Error::InvalidRequest(e.to_string())),
+            }
+        }
+        self
+    }
+
+    /// Serialize a value as the JSON body
+    pub(crate) fn json<T: Serialize + ?Sized>(mut self, value: &T) -> Self {
+        if let Ok(request) = &mut self.request {
+            match serde_json::to_vec(value) {
+                Ok(body) => request.body = Some(body),
+                Err(e) => self.request = Err(e.into()),
+            }
+        }
+        self
+    }
+
+    pub(crate) fn build(self) -> Result<HttpRequest> {
+        self.request
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_builder_query() {
+        let request = RequestBuilder::new(Method::Get, "https://api.test.com/fetch/subscribers?site_uuid=abc")
+            .query(&[("email", "test+1@example.com")])
+            .build()
+            .unwrap();
+
+        assert_eq!(request.url, "https://api.test.com/fetch/subscribers?site_uuid=abc&email=test%2B1%40example.com");
+        assert_eq!(request.path(), "/fetch/subscribers");
+    }
+
+    #[test]
+    fn test_request_builder_json() {
+        let request = RequestBuilder::new(Method::Post, "https://api.test.com/batch/events")
+            .json(&serde_json::json!({ "events": [] }))
+            .build()
+            .unwrap();
+
+        assert_eq!(request.body.as_deref(), Some(br#"{"events":[]}"#.as_slice()));
+    }
+
+    #[test]
+    fn test_request_builder_invalid_url() {
+        let request = RequestBuilder::new(Method::Get, "not a url")
+            .query(&[("a", "b")])
+            .build();
+
+        assert!(matches!(request, Err(Error::InvalidRequest(_))));
+    }
+
+    #[test]
+    fn test_request_debug_redacts_credentials() {
+        let mut request = HttpRequest::new(Method::Get, "https://api.test.com/fetch/tags");
+        request.headers.push(("Authorization".into(), "Basic c2VjcmV0".into()));
+        request.headers.push(("Accept".into(), "application/json".into()));
+
+        let debug = format!("{:?}", request);
+        assert!(!debug.contains("c2VjcmV0"), "{}", debug);
+        assert!(debug.contains("[REDACTED]"));
+        assert!(debug.contains("application/json"));
+        assert_eq!(request.header("authorization"), Some("Basic c2VjcmV0"));
+    }
+
+    #[test]
+    fn test_response_helpers() {
+        let response = HttpResponse::json_body(200, &serde_json::json!({ "results": 1 }))
+            .unwrap()
+            .with_header("Retry-After", "5");
+
+        assert!(response.is_success());
+        assert_eq!(response.header("retry-after"), Some("5"));
+        assert_eq!(response.header("content-type"), Some("application/json"));
+
+        let value: serde_json::Value = response.json().unwrap();
+        assert_eq!(value["results"], 1);
+    }
+}
\ No newline at end of file
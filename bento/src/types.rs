@@ -1,3 +1,4 @@
+use crate::EmailAddress;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use time::OffsetDateTime;
@@ -12,46 +13,223 @@ pub enum BroadcastType {
     Raw,
 }
 
-/// Type for subscriber commands
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum CommandType {
+/// A command to run against a subscriber
+///
+/// Each variant carries the payload its command needs, and serializes to the
+/// `{ "command", "email", "query" }` shape accepted by `/fetch/commands`.
+///
+/// # Example
+/// ```
+/// use bento::{Command, EmailAddress};
+///
+/// let email: EmailAddress = "user@example.com".parse()?;
+/// let commands = vec![
+///     Command::add_tag(email.clone(), "customer"),
+///     Command::add_field(email.clone(), "plan", "pro"),
+///     Command::change_email(email, "new@example.com".parse()?),
+/// ];
+/// # Ok::<(), bento::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "CommandWire", into = "CommandWire")]
+pub enum Command {
     /// Add a tag to a subscriber
-    AddTag,
+    AddTag {
+        /// Subscriber email
+        email: EmailAddress,
+        /// Tag name
+        tag: String,
+    },
+    /// Add a tag via an event, triggering tag-based automations
+    AddTagViaEvent {
+        /// Subscriber email
+        email: EmailAddress,
+        /// Tag name
+        tag: String,
+    },
+    /// Remove a tag from a subscriber
+    RemoveTag {
+        /// Subscriber email
+        email: EmailAddress,
+        /// Tag name
+        tag: String,
+    },
+    /// Set a custom field on a subscriber
+    AddField {
+        /// Subscriber email
+        email: EmailAddress,
+        /// Field key
+        key: String,
+        /// Field value
+        value: serde_json::Value,
+    },
+    /// Remove a custom field from a subscriber
+    RemoveField {
+        /// Subscriber email
+        email: EmailAddress,
+        /// Field key
+        key: String,
+    },
+    /// Subscribe a user
+    Subscribe {
+        /// Subscriber email
+        email: EmailAddress,
+    },
+    /// Unsubscribe a user
+    Unsubscribe {
+        /// Subscriber email
+        email: EmailAddress,
+    },
+    /// Change a user's email
+    ChangeEmail {
+        /// Current subscriber email
+        email: EmailAddress,
+        /// Email to change to
+        new_email: EmailAddress,
+    },
+}
+
+impl Command {
+    /// Add a tag to a subscriber
+    pub fn add_tag(email: EmailAddress, tag: impl Into<String>) -> Self {
+        Self::AddTag { email, tag: tag.into() }
+    }
+
     /// Add a tag via an event
-    AddTagViaEvent,
+    pub fn add_tag_via_event(email: EmailAddress, tag: impl Into<String>) -> Self {
+        Self::AddTagViaEvent { email, tag: tag.into() }
+    }
+
     /// Remove a tag from a subscriber
+    pub fn remove_tag(email: EmailAddress, tag: impl Into<String>) -> Self {
+        Self::RemoveTag { email, tag: tag.into() }
+    }
+
+    /// Set a custom field on a subscriber
+    pub fn add_field(email: EmailAddress, key: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        Self::AddField {
+            email,
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+
+    /// Remove a custom field from a subscriber
+    pub fn remove_field(email: EmailAddress, key: impl Into<String>) -> Self {
+        Self::RemoveField { email, key: key.into() }
+    }
+
+    /// Subscribe a user
+    pub fn subscribe(email: EmailAddress) -> Self {
+        Self::Subscribe { email }
+    }
+
+    /// Unsubscribe a user
+    pub fn unsubscribe(email: EmailAddress) -> Self {
+        Self::Unsubscribe { email }
+    }
+
+    /// Change a user's email from `old` to `new`
+    pub fn change_email(old: EmailAddress, new: EmailAddress) -> Self {
+        Self::ChangeEmail { email: old, new_email: new }
+    }
+
+    /// Get the email of the subscriber the command applies to
+    pub fn email(&self) -> &EmailAddress {
+        match self {
+            Self::AddTag { email, .. }
+            | Self::AddTagViaEvent { email, .. }
+            | Self::RemoveTag { email, .. }
+            | Self::AddField { email, .. }
+            | Self::RemoveField { email, .. }
+            | Self::Subscribe { email }
+            | Self::Unsubscribe { email }
+            | Self::ChangeEmail { email, .. } => email,
+        }
+    }
+}
+
+/// Wire name of a subscriber command
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum CommandName {
+    AddTag,
+    AddTagViaEvent,
     RemoveTag,
-    /// Add a field to a subscriber
     AddField,
-    /// Remove a field from a subscriber
     RemoveField,
-    /// Subscribe a user
     Subscribe,
-    /// Unsubscribe a user
     Unsubscribe,
-    /// Change a user's email
     ChangeEmail,
 }
 
-/// Data for a subscriber command
+/// Command as sent to `/fetch/commands`
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CommandData {
-    /// Type of command to execute
-    pub command: CommandType,
-    /// Email address of the subscriber
-    pub email: String,
-    /// Query or value for the command
-    pub query: String,
+struct CommandWire {
+    command: CommandName,
+    email: EmailAddress,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    query: Option<String>,
 }
 
-/// Command execution response
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CommandResponse {
-    /// Number of successful operations
-    pub results: u32,
-    /// Number of failed operations
-    pub failed: u32,
+/// JSON-encoded `query` of an `add_field` command
+#[derive(Debug, Serialize, Deserialize)]
+struct FieldQuery {
+    key: String,
+    value: serde_json::Value,
+}
+
+impl From<Command> for CommandWire {
+    fn from(command: Command) -> Self {
+        let (command, email, query) = match command {
+            Command::AddTag { email, tag } => (CommandName::AddTag, email, Some(tag)),
+            Command::AddTagViaEvent { email, tag } => (CommandName::AddTagViaEvent, email, Some(tag)),
+            Command::RemoveTag { email, tag } => (CommandName::RemoveTag, email, Some(tag)),
+            Command::AddField { email, key, value } => {
+                let query = serde_json::to_string(&FieldQuery { key, value })
+                    .expect("field query is always serializable");
+                (CommandName::AddField, email, Some(query))
+            }
+            Command::RemoveField { email, key } => (CommandName::RemoveField, email, Some(key)),
+            // The API has always received a non-empty query, the command name, for these
+            Command::Subscribe { email } => (CommandName::Subscribe, email, Some("subscribe".into())),
+            Command::Unsubscribe { email } => (CommandName::Unsubscribe, email, Some("unsubscribe".into())),
+            Command::ChangeEmail { email, new_email } => {
+                (CommandName::ChangeEmail, email, Some(new_email.into_inner()))
+            }
+        };
+
+        Self { command, email, query }
+    }
+}
+
+impl TryFrom<CommandWire> for Command {
+    type Error = crate::Error;
+
+    fn try_from(wire: CommandWire) -> crate::Result<Self> {
+        let CommandWire { command, email, query } = wire;
+
+        let query = || query.clone()
+            .ok_or_else(|| crate::Error::InvalidCommand("Command query is required".into()));
+
+        Ok(match command {
+            CommandName::AddTag => Self::AddTag { email, tag: query()? },
+            CommandName::AddTagViaEvent => Self::AddTagViaEvent { email, tag: query()? },
+            CommandName::RemoveTag => Self::RemoveTag { email, tag: query()? },
+            CommandName::AddField => {
+                let FieldQuery { key, value } = serde_json::from_str(&query()?)
+                    .map_err(|e| crate::Error::InvalidCommand(format!("Invalid add_field query: {}", e)))?;
+                Self::AddField { email, key, value }
+            }
+            CommandName::RemoveField => Self::RemoveField { email, key: query()? },
+            CommandName::Subscribe => Self::Subscribe { email },
+            CommandName::Unsubscribe => Self::Unsubscribe { email },
+            CommandName::ChangeEmail => Self::ChangeEmail {
+                email,
+                new_email: EmailAddress::parse(&query()?)?,
+            },
+        })
+    }
 }
 
 /// Request body for batch event tracking
@@ -69,7 +247,7 @@ pub struct EventData {
     #[serde(rename = "type")]
     pub event_type: String,
     /// Subscriber email
-    pub email: String,
+    pub email: EmailAddress,
     /// Additional fields
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fields: Option<HashMap<String, serde_json::Value>>,
@@ -85,7 +263,7 @@ pub struct ContactData {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     /// Contact email
-    pub email: String,
+    pub email: EmailAddress,
 }
 
 /// Broadcast message data
@@ -119,9 +297,9 @@ pub struct BroadcastData {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmailData {
     /// Recipient email
-    pub to: String,
+    pub to: EmailAddress,
     /// Sender email
-    pub from: String,
+    pub from: EmailAddress,
     /// Email subject
     pub subject: String,
     /// HTML content
@@ -154,14 +332,14 @@ pub struct CreateSubscriberData {
     ///
     /// Must be a valid email address format. This is the unique identifier
     /// for subscribers in the system.
-    pub email: String,
+    pub email: EmailAddress,
 }
 
 /// Import subscriber request data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImportSubscriberData {
     /// Subscriber email
-    pub email: String,
+    pub email: EmailAddress,
     /// First name
     #[serde(skip_serializing_if = "Option::is_none")]
     pub first_name: Option<String>,
@@ -179,15 +357,95 @@ pub struct ImportSubscriberData {
     pub custom_fields: HashMap<String, serde_json::Value>,
 }
 
-/// Response from a batch subscriber import operation
+/// Outcome of a batch operation
 ///
-/// Contains information about the success and failure counts from a bulk subscriber import.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ImportSubscriberResponse {
-    /// Number of subscribers successfully imported
-    pub results: u32,
-    /// Number of subscribers that failed to import
+/// Returned by the batch endpoints (`/batch/subscribers`, `/batch/events`, `/batch/emails`
+/// and `/fetch/commands`). A batch in which some items failed is still a successful
+/// request; check `failed` and `errors` to reconcile the result.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BatchOutcome {
+    /// Number of items processed successfully
+    #[serde(alias = "results")]
+    pub succeeded: u32,
+    /// Number of items that failed
+    #[serde(default)]
     pub failed: u32,
+    /// Per-item error detail, when the API provides it
+    #[serde(default)]
+    pub errors: Vec<BatchItemError>,
+}
+
+impl BatchOutcome {
+    /// Returns true if no items failed
+    pub fn is_complete_success(&self) -> bool {
+        self.failed == 0
+    }
+
+    /// Total number of items the API reported on
+    pub fn total(&self) -> u32 {
+        self.succeeded + self.failed
+    }
+
+    /// Add the counts and errors of another outcome to this one
+    pub fn merge(&mut self, other: BatchOutcome) {
+        self.succeeded += other.succeeded;
+        self.failed += other.failed;
+        self.errors.extend(other.errors);
+    }
+}
+
+/// Error detail for a single item of a batch
+///
+/// The API reports errors either as plain messages or as objects; plain messages
+/// are stored in `message`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "RawBatchItemError")]
+pub struct BatchItemError {
+    /// Zero-based position of the item in the batch, if provided
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+    /// Email address of the item, if provided
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// Error message, if provided
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Any other fields of the error
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawBatchItemError {
+    Message(String),
+    Detail {
+        #[serde(default)]
+        index: Option<usize>,
+        #[serde(default)]
+        email: Option<String>,
+        #[serde(default, alias = "error")]
+        message: Option<String>,
+        #[serde(flatten)]
+        extra: HashMap<String, serde_json::Value>,
+    },
+}
+
+impl From<RawBatchItemError> for BatchItemError {
+    fn from(raw: RawBatchItemError) -> Self {
+        match raw {
+            RawBatchItemError::Message(message) => Self {
+                message: Some(message),
+                ..Self::default()
+            },
+            RawBatchItemError::Detail { index, email, message, extra } => Self {
+                index,
+                email,
+                message,
+                extra,
+            },
+        }
+    }
 }
 
 /// Subscriber data returned from the API
@@ -209,7 +467,7 @@ pub struct SubscriberData {
 ///
 /// Contains all the mutable and configurable properties of a subscriber,
 /// including their contact information, custom fields, tags, and subscription status.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct SubscriberAttributes {
     /// Unique UUID for the subscriber
     pub uuid: String,
@@ -0,0 +1,509 @@
+//! Bulk email validation
+//!
+//! [`BulkValidator`] cleans large address lists before an import. Each address is
+//! checked locally first, for syntax and against a list of disposable domains; only
+//! the addresses that pass are sent to `/experimental/validation`, with bounded
+//! concurrency. API results are cached by address so repeated runs over overlapping
+//! lists do not validate the same address twice.
+
+use crate::experimental::{ValidationData, ValidationResponse};
+use crate::{Client, EmailAddress, Error, Result};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::{instrument, warn};
+
+/// Disposable email domains rejected without calling the API
+const DISPOSABLE_DOMAINS: &[&str] = &[
+    "10minutemail.com",
+    "dispostable.com",
+    "fakeinbox.com",
+    "getnada.com",
+    "guerrillamail.com",
+    "mailinator.com",
+    "maildrop.cc",
+    "mintemail.com",
+    "sharklasers.com",
+    "temp-mail.org",
+    "tempmail.com",
+    "throwawaymail.com",
+    "trashmail.com",
+    "yopmail.com",
+];
+
+/// Verdict for one address
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    /// The API reported the address as valid
+    Valid,
+    /// The API reported the address as invalid
+    Invalid,
+    /// The address is not syntactically valid; the API was not called
+    InvalidSyntax,
+    /// The address uses a disposable domain; the API was not called
+    Disposable,
+    /// The API call failed, so the address could not be checked
+    Unverified,
+}
+
+impl Verdict {
+    /// Get the verdict as it appears in reports, e.g. `invalid_syntax`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Verdict::Valid => "valid",
+            Verdict::Invalid => "invalid",
+            Verdict::InvalidSyntax => "invalid_syntax",
+            Verdict::Disposable => "disposable",
+            Verdict::Unverified => "unverified",
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Result of validating one address
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmailVerdict {
+    /// Address as given in the input
+    pub email: String,
+    /// Outcome of the checks
+    pub verdict: Verdict,
+    /// Reasons from the API, or a description of the local check or error
+    pub reasons: Vec<String>,
+    /// Whether the API result came from the cache
+    pub cached: bool,
+}
+
+/// Per-address results of [`BulkValidator::validate`], in input order
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValidationReport {
+    /// One verdict per input address, including duplicates
+    pub verdicts: Vec<EmailVerdict>,
+}
+
+impl ValidationReport {
+    /// Count the addresses with the given verdict
+    pub fn count(&self, verdict: Verdict) -> usize {
+        self.verdicts.iter().filter(|v| v.verdict == verdict).count()
+    }
+
+    /// Iterate over the addresses the API reported as valid
+    pub fn valid(&self) -> impl Iterator<Item = &str> {
+        self.verdicts.iter().filter(|v| v.verdict == Verdict::Valid).map(|v| v.email.as_str())
+    }
+
+    /// Write the report as CSV with the columns `email,verdict,reasons,cached`
+    ///
+    /// Multiple reasons are joined with `; `.
+    pub fn write_csv<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "email,verdict,reasons,cached")?;
+        for verdict in &self.verdicts {
+            writeln!(
+                writer,
+                "{},{},{},{}",
+                csv_field(&verdict.email),
+                verdict.verdict,
+                csv_field(&verdict.reasons.join("; ")),
+                verdict.cached
+            )?;
+        }
+        writer.flush()
+    }
+}
+
+struct CacheEntry {
+    response: ValidationResponse,
+    expires_at: Instant,
+}
+
+/// Validates address lists with local pre-checks, bounded concurrency and a result cache
+///
+/// Cloning a validator returns another handle to the same cache.
+///
+/// # Example
+/// ```no_run
+/// # async fn example(client: bento::Client, addresses: Vec<String>) -> Result<(), Box<dyn std::error::Error>> {
+/// use bento::validation::{BulkValidator, Verdict};
+/// use std::time::Duration;
+///
+/// let validator = BulkValidator::builder(client)
+///     .concurrency(8)
+///     .cache_ttl(Duration::from_secs(24 * 60 * 60))
+///     .disposable_domain("throwaway.example")
+///     .build()?;
+///
+/// let report = validator.validate(addresses).await;
+/// println!("{} valid, {} disposable", report.count(Verdict::Valid), report.count(Verdict::Disposable));
+/// report.write_csv(std::fs::File::create("verdicts.csv")?)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct BulkValidator {
+    client: Client,
+    concurrency: usize,
+    cache_ttl: Duration,
+    disposable_domains: Arc<HashSet<String>>,
+    cache: Arc<Mutex<HashMap<String, CacheEntry>>>,
+}
+
+impl fmt::Debug for BulkValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BulkValidator")
+            .field("concurrency", &self.concurrency)
+            .field("cache_ttl", &self.cache_ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+impl BulkValidator {
+    /// Create a builder for a validator that calls the API through `client`
+    pub fn builder(client: Client) -> BulkValidatorBuilder {
+        BulkValidatorBuilder {
+            client,
+            concurrency: 4,
+            cache_ttl: Duration::from_secs(24 * 60 * 60),
+            disposable_domains: DISPOSABLE_DOMAINS.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    /// Validate every address, returning one verdict per input in input order
+    ///
+    /// Addresses that normalize to the same value are sent to the API once. Failed API
+    /// calls produce [`Verdict::Unverified`] and are not cached.
+    #[instrument(skip_all)]
+    pub async fn validate<I, S>(&self, addresses: I) -> ValidationReport
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let inputs: Vec<String> = addresses.into_iter().map(Into::into).collect();
+        let now = Instant::now();
+        self.lock_cache().retain(|_, entry| entry.expires_at > now);
+
+        // Local checks; survivors are keyed by their normalized address
+        let checked: Vec<std::result::Result<EmailAddress, EmailVerdict>> = inputs.iter()
+            .map(|input| self.check_locally(input))
+            .collect();
+
+        let mut pending: Vec<&EmailAddress> = Vec::new();
+        let mut seen: HashSet<&str> = HashSet::new();
+        let mut results: HashMap<&str, (std::result::Result<ValidationResponse, String>, bool)> = HashMap::new();
+        {
+            let cache = self.lock_cache();
+            for email in checked.iter().filter_map(|c| c.as_ref().ok()) {
+                if !seen.insert(email.as_str()) {
+                    continue;
+                }
+                match cache.get(email.as_str()) {
+                    Some(entry) => {
+                        results.insert(email.as_str(), (Ok(entry.response.clone()), true));
+                    }
+                    None => pending.push(email),
+                }
+            }
+        }
+
+        let mut responses = stream::iter(pending)
+            .map(|email| async move { (email, self.validate_one(email).await) })
+            .buffer_unordered(self.concurrency);
+
+        while let Some((email, result)) = responses.next().await {
+            if let Ok(response) = &result {
+                if !self.cache_ttl.is_zero() {
+                    self.lock_cache().insert(email.as_str().to_string(), CacheEntry {
+                        response: response.clone(),
+                        expires_at: Instant::now() + self.cache_ttl,
+                    });
+                }
+            }
+            results.insert(email.as_str(), (result.map_err(|e| e.to_string()), false));
+        }
+
+        let verdicts = inputs.into_iter()
+            .zip(&checked)
+            .map(|(input, checked)| match checked {
+                Err(verdict) => verdict.clone(),
+                Ok(email) => {
+                    let (result, cached) = &results[email.as_str()];
+                    match result {
+                        Ok(response) => EmailVerdict {
+                            email: input,
+                            verdict: if response.valid { Verdict::Valid } else { Verdict::Invalid },
+                            reasons: response.reasons.clone(),
+                            cached: *cached,
+                        },
+                        Err(error) => EmailVerdict {
+                            email: input,
+                            verdict: Verdict::Unverified,
+                            reasons: vec![error.clone()],
+                            cached: false,
+                        },
+                    }
+                }
+            })
+            .collect();
+
+        ValidationReport { verdicts }
+    }
+
+    /// Remove every cached result
+    pub fn clear_cache(&self) {
+        self.lock_cache().clear();
+    }
+
+    /// Runs the syntax and disposable-domain checks.
+    fn check_locally(&self, input: &str) -> std::result::Result<EmailAddress, EmailVerdict> {
+        let rejected = |verdict, reason: &str| EmailVerdict {
+            email: input.to_string(),
+            verdict,
+            reasons: vec![reason.to_string()],
+            cached: false,
+        };
+
+        let email = EmailAddress::parse(input)
+            .map_err(|_| rejected(Verdict::InvalidSyntax, "invalid email syntax"))?;
+        if self.is_disposable(email.domain()) {
+            return Err(rejected(Verdict::Disposable, "disposable email domain"));
+        }
+        Ok(email)
+    }
+
+    /// Matches the domain and every parent domain against the disposable list.
+    fn is_disposable(&self, domain: &str) -> bool {
+        let mut domain = domain;
+        loop {
+            if self.disposable_domains.contains(domain) {
+                return true;
+            }
+            match domain.split_once('.') {
+                Some((_, parent)) => domain = parent,
+                None => return false,
+            }
+        }
+    }
+
+    async fn validate_one(&self, email: &EmailAddress) -> Result<ValidationResponse> {
+        let data = ValidationData {
+            email: email.as_str().to_string(),
+            name: None,
+            user_agent: None,
+            ip: None,
+        };
+        let result = self.client.validate_email(&data).await;
+        if let Err(err) = &result {
+            warn!(error = %err, email = %self.client.redact(email.as_str()), "email validation failed");
+        }
+        result
+    }
+
+    fn lock_cache(&self) -> MutexGuard<'_, HashMap<String, CacheEntry>> {
+        self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Builder for [`BulkValidator`]
+pub struct BulkValidatorBuilder {
+    client: Client,
+    concurrency: usize,
+    cache_ttl: Duration,
+    disposable_domains: HashSet<String>,
+}
+
+impl fmt::Debug for BulkValidatorBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BulkValidatorBuilder")
+            .field("concurrency", &self.concurrency)
+            .field("cache_ttl", &self.cache_ttl)
+            .field("disposable_domains", &self.disposable_domains.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl BulkValidatorBuilder {
+    /// Set the number of API calls in flight at once (default 4)
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Set how long API results are cached (default 24h); zero disables the cache
+    pub fn cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
+    /// Treat a domain and its subdomains as disposable, in addition to the built-in list
+    pub fn disposable_domain(mut self, domain: impl Into<String>) -> Self {
+        self.disposable_domains.insert(domain.into().to_lowercase());
+        self
+    }
+
+    /// Treat several domains as disposable, in addition to the built-in list
+    pub fn disposable_domains<I, S>(mut self, domains: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.disposable_domains.extend(domains.into_iter().map(|d| d.into().to_lowercase()));
+        self
+    }
+
+    /// Build the validator
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidRequest` if the concurrency is 0.
+    pub fn build(self) -> Result<BulkValidator> {
+        if self.concurrency == 0 {
+            return Err(Error::InvalidRequest("Concurrency must be positive".into()));
+        }
+
+        Ok(BulkValidator {
+            client: self.client,
+            concurrency: self.concurrency,
+            cache_ttl: self.cache_ttl,
+            disposable_domains: Arc::new(self.disposable_domains),
+            cache: Arc::default(),
+        })
+    }
+}
+
+/// Quotes a CSV field when it contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::FakeBento;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn validation_requests(bento: &FakeBento) -> usize {
+        bento.requests().iter().filter(|r| r.path().ends_with("/experimental/validation")).count()
+    }
+
+    #[tokio::test]
+    async fn test_local_checks_and_deduplication() {
+        let bento = FakeBento::new();
+        let validator = BulkValidator::builder(bento.client())
+            .disposable_domain("Throwaway.example")
+            .build()
+            .unwrap();
+
+        let report = validator.validate([
+            "jane@example.com",
+            "not an email",
+            "bob@mailinator.com",
+            "bob@eu.throwaway.example",
+            "jane@EXAMPLE.com",
+        ]).await;
+
+        let verdicts: Vec<Verdict> = report.verdicts.iter().map(|v| v.verdict).collect();
+        assert_eq!(verdicts, vec![
+            Verdict::Valid,
+            Verdict::InvalidSyntax,
+            Verdict::Disposable,
+            Verdict::Disposable,
+            Verdict::Valid,
+        ]);
+        assert_eq!(report.verdicts[4].email, "jane@EXAMPLE.com");
+        assert_eq!(report.valid().count(), 2);
+        assert_eq!(validation_requests(&bento), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cache_expires_after_ttl() {
+        let bento = FakeBento::new();
+        let validator = BulkValidator::builder(bento.client())
+            .cache_ttl(Duration::from_secs(60))
+            .build()
+            .unwrap();
+
+        let report = validator.validate(["jane@example.com"]).await;
+        assert!(!report.verdicts[0].cached);
+
+        let report = validator.validate(["jane@example.com"]).await;
+        assert!(report.verdicts[0].cached);
+        assert_eq!(validation_requests(&bento), 1);
+
+        tokio::time::advance(Duration::from_secs(61)).await;
+        let report = validator.validate(["jane@example.com"]).await;
+        assert!(!report.verdicts[0].cached);
+        assert_eq!(validation_requests(&bento), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failures_are_unverified_and_not_cached() {
+        let bento = FakeBento::new();
+        let validator = BulkValidator::builder(bento.client()).build().unwrap();
+
+        bento.fail_next(503);
+        let report = validator.validate(["jane@example.com"]).await;
+        assert_eq!(report.verdicts[0].verdict, Verdict::Unverified);
+        assert_eq!(report.count(Verdict::Unverified), 1);
+
+        let report = validator.validate(["jane@example.com"]).await;
+        assert_eq!(report.verdicts[0].verdict, Verdict::Valid);
+        assert!(!report.verdicts[0].cached);
+    }
+
+    #[tokio::test]
+    async fn test_api_reasons_and_csv() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/experimental/validation"))
+            .and(body_partial_json(serde_json::json!({ "email": "bounce@example.com" })))
+            .respond_with(ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({
+                    "valid": false,
+                    "reasons": ["mailbox does not exist", "domain has \"catch-all\", disabled"]
+                })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/experimental/validation"))
+            .respond_with(ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "valid": true, "reasons": [] })))
+            .mount(&mock_server)
+            .await;
+
+        let client = crate::test_utils::create_test_client(mock_server.uri());
+        let validator = BulkValidator::builder(client).concurrency(2).build().unwrap();
+        let report = validator.validate(["ok@example.com", "bounce@example.com", "x@"]).await;
+
+        assert_eq!(report.verdicts[1].verdict, Verdict::Invalid);
+        assert_eq!(report.verdicts[1].reasons.len(), 2);
+
+        let mut csv = Vec::new();
+        report.write_csv(&mut csv).unwrap();
+        assert_eq!(String::from_utf8(csv).unwrap(), concat!(
+            "email,verdict,reasons,cached\n",
+            "ok@example.com,valid,,false\n",
+            "bounce@example.com,invalid,\"mailbox does not exist; domain has \"\"catch-all\"\", disabled\",false\n",
+            "x@,invalid_syntax,invalid email syntax,false\n",
+        ));
+    }
+
+    #[test]
+    fn test_builder_validation() {
+        let result = BulkValidator::builder(FakeBento::new().client()).concurrency(0).build();
+        assert!(matches!(result, Err(Error::InvalidRequest(_))));
+    }
+}
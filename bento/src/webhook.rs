@@ -0,0 +1,303 @@
+//! Verifying webhook deliveries
+//!
+//! A [`WebhookVerifier`] checks the HMAC-SHA256 signature of a delivery against the
+//! raw request body and returns the body as JSON.
+//!
+//! The SDK does not assume a signing format: Bento's webhook signature header and
+//! scheme are not specified here, so the caller names the header and picks the
+//! [`SignatureScheme`] that matches the deliveries their account receives. Event
+//! payloads are likewise returned as untyped [`serde_json::Value`]s.
+
+use crate::{Error, Result};
+use hmac::{Hmac, Mac};
+use secrecy::zeroize::Zeroizing;
+use sha2::Sha256;
+use std::fmt;
+use std::time::Duration;
+use time::OffsetDateTime;
+
+/// Default maximum age of a timestamped delivery
+const DEFAULT_TOLERANCE: Duration = Duration::from_secs(300);
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Error type returned by webhook handlers
+pub type HandlerError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Result type returned by webhook handlers
+pub type HandlerResult = std::result::Result<(), HandlerError>;
+
+/// How a delivery's signature is computed and encoded in the signature header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SignatureScheme {
+    /// `t=<unix timestamp>,v1=<hex HMAC-SHA256 of "<timestamp>.<body>">`
+    ///
+    /// The timestamp is checked against the verifier's tolerance. Several `v1`
+    /// entries may be present while a secret is being rotated; the delivery is
+    /// accepted if any of them matches.
+    Timestamped,
+    /// The hex-encoded HMAC-SHA256 of the raw body, optionally prefixed with `sha256=`
+    ///
+    /// The header carries no timestamp, so deliveries cannot be checked for replays.
+    Body,
+}
+
+/// Verifies the authenticity of webhook deliveries
+///
+/// # Example
+/// ```
+/// use bento::webhook::{SignatureScheme, WebhookVerifier};
+///
+/// let verifier = WebhookVerifier::new("whsec_test", "X-Webhook-Signature", SignatureScheme::Body);
+/// let body = br#"{"type":"ping"}"#;
+/// let signature = verifier.sign(0, body);
+///
+/// let payload = verifier.verify_and_parse(&signature, body)?;
+/// assert_eq!(payload["type"], "ping");
+/// # Ok::<(), bento::Error>(())
+/// ```
+#[derive(Clone)]
+pub struct WebhookVerifier {
+    secret: Zeroizing<Vec<u8>>,
+    header: String,
+    scheme: SignatureScheme,
+    tolerance: Duration,
+}
+
+impl fmt::Debug for WebhookVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebhookVerifier")
+            .field("header", &self.header)
+            .field("scheme", &self.scheme)
+            .field("tolerance", &self.tolerance)
+            .finish_non_exhaustive()
+    }
+}
+
+impl WebhookVerifier {
+    /// Create a verifier for signatures sent in `header` using `scheme`
+    ///
+    /// Timestamped deliveries older than five minutes are rejected by default. The
+    /// secret is zeroized when the verifier is dropped.
+    pub fn new(secret: impl Into<Vec<u8>>, header: impl Into<String>, scheme: SignatureScheme) -> Self {
+        Self {
+            secret: Zeroizing::new(secret.into()),
+            header: header.into(),
+            scheme,
+            tolerance: DEFAULT_TOLERANCE,
+        }
+    }
+
+    /// Set the maximum difference between the signature timestamp and the current time
+    ///
+    /// Ignored by schemes without a timestamp.
+    pub fn tolerance(mut self, tolerance: Duration) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Get the name of the header carrying the signature
+    pub fn header_name(&self) -> &str {
+        &self.header
+    }
+
+    /// Get the scheme signatures are checked with
+    pub fn signature_scheme(&self) -> SignatureScheme {
+        self.scheme
+    }
+
+    /// Compute the signature header value for a body in the configured scheme
+    ///
+    /// `timestamp` is ignored by schemes without one. Useful for testing webhook
+    /// endpoints.
+    pub fn sign(&self, timestamp: i64, body: &[u8]) -> String {
+        match self.scheme {
+            SignatureScheme::Timestamped => {
+                let signature = hex::encode(self.mac(Some(timestamp), body).finalize().into_bytes());
+                format!("t={},v1={}", timestamp, signature)
+            }
+            SignatureScheme::Body => hex::encode(self.mac(None, body).finalize().into_bytes()),
+        }
+    }
+
+    /// Check the signature header of a delivery against its raw body
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidSignature` if the header is malformed, the timestamp is
+    /// outside the tolerance, or no signature matches.
+    pub fn verify(&self, signature: &str, body: &[u8]) -> Result<()> {
+        self.verify_at(signature, body, OffsetDateTime::now_utc())
+    }
+
+    /// Verify a delivery and parse its body as JSON
+    ///
+    /// # Errors
+    /// Returns `Error::InvalidSignature` if verification fails, or `Error::Json` if the
+    /// body is not valid JSON.
+    pub fn verify_and_parse(&self, signature: &str, body: &[u8]) -> Result<serde_json::Value> {
+        self.verify(signature, body)?;
+        Ok(serde_json::from_slice(body)?)
+    }
+
+    fn verify_at(&self, signature: &str, body: &[u8], now: OffsetDateTime) -> Result<()> {
+        match self.scheme {
+            SignatureScheme::Timestamped => self.verify_timestamped(signature, body, now),
+            SignatureScheme::Body => self.verify_body(signature, body),
+        }
+    }
+
+    fn verify_timestamped(&self, signature: &str, body: &[u8], now: OffsetDateTime) -> Result<()> {
+        let mut timestamp = None;
+        let mut signatures = Vec::new();
+        for part in signature.split(',') {
+            match part.trim().split_once('=') {
+                Some(("t", value)) => timestamp = value.parse::<i64>().ok(),
+                Some(("v1", value)) => signatures.extend(hex::decode(value).ok()),
+                _ => {}
+            }
+        }
+
+        let timestamp = timestamp
+            .ok_or_else(|| Error::InvalidSignature("missing or invalid timestamp".into()))?;
+        if signatures.is_empty() {
+            return Err(Error::InvalidSignature("no v1 signature".into()));
+        }
+
+        let age = now.unix_timestamp().abs_diff(timestamp);
+        if age > self.tolerance.as_secs() {
+            return Err(Error::InvalidSignature(format!("timestamp is {}s from the current time", age)));
+        }
+
+        let matches = signatures.iter()
+            .any(|expected| self.mac(Some(timestamp), body).verify_slice(expected).is_ok());
+        if !matches {
+            return Err(Error::InvalidSignature("signature does not match".into()));
+        }
+
+        Ok(())
+    }
+
+    fn verify_body(&self, signature: &str, body: &[u8]) -> Result<()> {
+        let signature = signature.trim();
+        let signature = signature.strip_prefix("sha256=").unwrap_or(signature);
+        let expected = hex::decode(signature)
+            .map_err(|_| Error::InvalidSignature("signature is not hex".into()))?;
+
+        self.mac(None, body).verify_slice(&expected)
+            .map_err(|_| Error::InvalidSignature("signature does not match".into()))
+    }
+
+    fn mac(&self, timestamp: Option<i64>, body: &[u8]) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.secret)
+            .expect("HMAC accepts keys of any length");
+        if let Some(timestamp) = timestamp {
+            mac.update(timestamp.to_string().as_bytes());
+            mac.update(b".");
+        }
+        mac.update(body);
+        mac
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const HEADER: &str = "X-Webhook-Signature";
+
+    fn timestamped(secret: &str) -> WebhookVerifier {
+        WebhookVerifier::new(secret, HEADER, SignatureScheme::Timestamped)
+    }
+
+    fn body() -> Vec<u8> {
+        serde_json::to_vec(&json!({ "type": "ping", "data": { "email": "test@example.com" } })).unwrap()
+    }
+
+    fn signed(verifier: &WebhookVerifier, body: &[u8]) -> String {
+        verifier.sign(OffsetDateTime::now_utc().unix_timestamp(), body)
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let verifier = timestamped("secret");
+        let body = body();
+        let signature = signed(&verifier, &body);
+
+        assert!(verifier.verify(&signature, &body).is_ok());
+
+        let mut tampered = body.clone();
+        tampered[10] ^= 1;
+        assert!(matches!(verifier.verify(&signature, &tampered), Err(Error::InvalidSignature(_))));
+
+        let other = timestamped("other-secret");
+        assert!(matches!(other.verify(&signature, &body), Err(Error::InvalidSignature(_))));
+
+        for malformed in ["", "t=abc,v1=00", "v1=00", "t=1700000000"] {
+            assert!(matches!(verifier.verify(malformed, &body), Err(Error::InvalidSignature(_))));
+        }
+    }
+
+    #[test]
+    fn test_verify_timestamp_tolerance() {
+        let verifier = timestamped("secret").tolerance(Duration::from_secs(60));
+        let body = b"{}";
+        let now = OffsetDateTime::now_utc();
+        let signature = verifier.sign(now.unix_timestamp() - 120, body);
+
+        assert!(matches!(verifier.verify_at(&signature, body, now), Err(Error::InvalidSignature(_))));
+        assert!(verifier.verify_at(&signature, body, now - Duration::from_secs(90)).is_ok());
+    }
+
+    #[test]
+    fn test_verify_rotated_secret() {
+        let old = timestamped("old");
+        let new = timestamped("new");
+        let body = b"{}";
+        let timestamp = OffsetDateTime::now_utc().unix_timestamp();
+        let new_signature = new.sign(timestamp, body);
+        let header = format!("{},{}", old.sign(timestamp, body), new_signature.split_once(',').unwrap().1);
+
+        assert!(new.verify(&header, body).is_ok());
+        assert!(old.verify(&header, body).is_ok());
+    }
+
+    #[test]
+    fn test_verify_body_scheme() {
+        let verifier = WebhookVerifier::new("secret", HEADER, SignatureScheme::Body);
+        let body = body();
+        let signature = verifier.sign(0, &body);
+
+        // Computed independently of the verifier
+        let mut mac = HmacSha256::new_from_slice(b"secret").unwrap();
+        mac.update(&body);
+        assert_eq!(signature, hex::encode(mac.finalize().into_bytes()));
+
+        assert!(verifier.verify(&signature, &body).is_ok());
+        assert!(verifier.verify(&format!("sha256={}", signature), &body).is_ok());
+
+        let mut tampered = body.clone();
+        tampered[10] ^= 1;
+        assert!(matches!(verifier.verify(&signature, &tampered), Err(Error::InvalidSignature(_))));
+
+        let timestamped = timestamped("secret");
+        assert!(matches!(verifier.verify(&signed(&timestamped, &body), &body), Err(Error::InvalidSignature(_))));
+        assert!(matches!(timestamped.verify(&signature, &body), Err(Error::InvalidSignature(_))));
+    }
+
+    #[test]
+    fn test_verify_and_parse() {
+        let verifier = timestamped("secret");
+        let body = body();
+
+        let payload = verifier.verify_and_parse(&signed(&verifier, &body), &body).unwrap();
+        assert_eq!(payload["data"]["email"], "test@example.com");
+
+        let result = verifier.verify_and_parse(&signed(&verifier, b"not json"), b"not json");
+        assert!(matches!(result, Err(Error::Json(_))));
+
+        let result = verifier.verify_and_parse("t=1,v1=00", &body);
+        assert!(matches!(result, Err(Error::InvalidSignature(_))));
+    }
+}
@@ -0,0 +1,39 @@
+//! Locating API credentials in the environment or a profile file.
+
+use bento::{Config, ConfigBuilder};
+use std::env;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+/// Profile used when `--profile` is not given and no credentials are set in the environment
+const DEFAULT_PROFILE: &str = "default";
+
+/// Get the default profile file path, `~/.bento/config.toml`
+pub fn default_path() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".bento").join("config.toml"))
+}
+
+/// Resolve the client configuration
+///
+/// An explicit profile is read from the profile file. Otherwise the `BENTO_*`
+/// environment variables are used when `BENTO_PUBLISHABLE_KEY` is set, falling back
+/// to the `default` profile.
+pub fn load(profile: Option<&str>, path: Option<&Path>) -> Result<Config, Box<dyn Error>> {
+    let default_path = default_path();
+    let path = path.or(default_path.as_deref());
+
+    let builder = match (profile, path) {
+        (Some(_), None) => return Err("cannot locate the profile file; pass --config".into()),
+        (Some(profile), Some(path)) => ConfigBuilder::from_file(path, profile)?,
+        _ if env::var_os("BENTO_PUBLISHABLE_KEY").is_some() => ConfigBuilder::from_env()?,
+        (None, Some(path)) if path.exists() => ConfigBuilder::from_file(path, DEFAULT_PROFILE)?,
+        _ => {
+            return Err("no credentials found: set BENTO_PUBLISHABLE_KEY, BENTO_SECRET_KEY and \
+                        BENTO_SITE_UUID, or pass --profile".into())
+        }
+    };
+
+    Ok(builder.build()?)
+}
\ No newline at end of file
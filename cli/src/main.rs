@@ -0,0 +1,549 @@
+//! Command-line interface for the Bento API
+//!
+//! Wraps every `bento::Client` method as a subcommand. Credentials are read from
+//! the `BENTO_*` environment variables or from a named profile in
+//! `~/.bento/config.toml`.
+
+mod credentials;
+mod output;
+
+use bento::email::{EmailBatch, MAX_BATCH_SIZE};
+use bento::experimental::{BlacklistData, ValidationData};
+use bento::prometheus::StatsExporter;
+use bento::subscriber::BulkImportOptions;
+use bento::{
+    BatchOutcome, BroadcastData, Client, Command as BentoCommand, EmailAddress, EmailData, EventData,
+    ImportSubscriberData,
+};
+use clap::{Args, Parser, Subcommand};
+use output::Format;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::io::{self, Read};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+#[derive(Debug, Parser)]
+#[command(name = "bento", version, about = "Command-line interface for the Bento API")]
+struct Cli {
+    /// Profile to load from the profile file
+    #[arg(long, short, global = true, env = "BENTO_PROFILE")]
+    profile: Option<String>,
+
+    /// Profile file [default: ~/.bento/config.toml]
+    #[arg(long, global = true, env = "BENTO_CONFIG")]
+    config: Option<PathBuf>,
+
+    /// Output format
+    #[arg(long, short, global = true, value_enum, default_value_t = Format::Table)]
+    output: Format,
+
+    #[command(subcommand)]
+    command: Resource,
+}
+
+#[derive(Debug, Subcommand)]
+enum Resource {
+    /// Find, create and import subscribers
+    #[command(subcommand)]
+    Subscribers(SubscriberCommand),
+    /// Track events
+    #[command(subcommand)]
+    Events(EventCommand),
+    /// Run subscriber commands
+    #[command(subcommand)]
+    Commands(CommandsCommand),
+    /// Send transactional emails
+    #[command(subcommand)]
+    Emails(EmailCommand),
+    /// List and create tags
+    #[command(subcommand)]
+    Tags(TagCommand),
+    /// List and create custom fields
+    #[command(subcommand)]
+    Fields(FieldCommand),
+    /// List and create broadcasts
+    #[command(subcommand)]
+    Broadcasts(BroadcastCommand),
+    /// Site, segment and report statistics
+    #[command(subcommand)]
+    Stats(StatsCommand),
+    /// Experimental validation and enrichment APIs
+    #[command(subcommand)]
+    Experimental(ExperimentalCommand),
+}
+
+#[derive(Debug, Subcommand)]
+enum SubscriberCommand {
+    /// Find a subscriber by email
+    Find {
+        /// Subscriber email
+        email: String,
+    },
+    /// Create a subscriber
+    Create {
+        /// Subscriber email
+        email: String,
+    },
+    /// Import subscribers from a JSON array of import records
+    Import {
+        /// JSON file, or `-` for stdin
+        file: PathBuf,
+        /// Subscribers per request (1-1000)
+        #[arg(long, default_value_t = 1000)]
+        chunk_size: usize,
+        /// Requests in flight at once
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum EventCommand {
+    /// Track one event from flags, or many from a JSON array
+    Track(TrackArgs),
+}
+
+#[derive(Debug, Args)]
+struct TrackArgs {
+    /// Event type, e.g. `$purchase`
+    #[arg(long = "type", required_unless_present = "file")]
+    event_type: Option<String>,
+    /// Subscriber email
+    #[arg(long, required_unless_present = "file")]
+    email: Option<String>,
+    /// Subscriber field as key=value; values are parsed as JSON when possible
+    #[arg(long = "field", value_name = "KEY=VALUE")]
+    fields: Vec<String>,
+    /// Event detail as key=value; values are parsed as JSON when possible
+    #[arg(long = "detail", value_name = "KEY=VALUE")]
+    details: Vec<String>,
+    /// JSON file of events, or `-` for stdin
+    #[arg(long, conflicts_with_all = ["event_type", "email"])]
+    file: Option<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+enum CommandsCommand {
+    /// Add a tag to a subscriber
+    AddTag { email: String, tag: String },
+    /// Add a tag via an event
+    AddTagViaEvent { email: String, tag: String },
+    /// Remove a tag from a subscriber
+    RemoveTag { email: String, tag: String },
+    /// Set a field on a subscriber; the value is parsed as JSON when possible
+    AddField { email: String, key: String, value: String },
+    /// Remove a field from a subscriber
+    RemoveField { email: String, key: String },
+    /// Subscribe a user
+    Subscribe { email: String },
+    /// Unsubscribe a user
+    Unsubscribe { email: String },
+    /// Change a user's email
+    ChangeEmail { old: String, new: String },
+    /// Run a JSON array of commands in the API's command format
+    Run {
+        /// JSON file, or `-` for stdin
+        file: PathBuf,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum EmailCommand {
+    /// Send emails from a JSON array, in batches of 60
+    Send {
+        /// JSON file, or `-` for stdin
+        file: PathBuf,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum TagCommand {
+    /// List tags
+    List,
+    /// Create a tag
+    Create { name: String },
+}
+
+#[derive(Debug, Subcommand)]
+enum FieldCommand {
+    /// List custom fields
+    List,
+    /// Create a custom field
+    Create { key: String },
+}
+
+#[derive(Debug, Subcommand)]
+enum BroadcastCommand {
+    /// List broadcasts
+    List,
+    /// Create broadcasts from a JSON array
+    Create {
+        /// JSON file, or `-` for stdin
+        file: PathBuf,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum StatsCommand {
+    /// Site statistics
+    Site,
+    /// Segment statistics
+    Segment { segment_id: String },
+    /// Report statistics
+    Report { report_id: String },
+    /// Serve site and segment statistics as Prometheus gauges until interrupted
+    Export {
+        /// Segment to export; repeat for several segments
+        #[arg(long = "segment", value_name = "SEGMENT_ID")]
+        segments: Vec<String>,
+        /// Address to serve `/metrics` on
+        #[arg(long, default_value = "127.0.0.1:9464")]
+        listen: SocketAddr,
+        /// Seconds between polls
+        #[arg(long, default_value_t = 60)]
+        interval: u64,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum ExperimentalCommand {
+    /// Check whether a domain or IP is on a blacklist
+    Blacklist {
+        #[arg(long, required_unless_present = "ip")]
+        domain: Option<String>,
+        #[arg(long)]
+        ip: Option<String>,
+    },
+    /// Validate an email address
+    Validate {
+        email: String,
+        /// Full name of the user
+        #[arg(long)]
+        name: Option<String>,
+        /// User agent of the signup
+        #[arg(long)]
+        user_agent: Option<String>,
+        /// IP address of the signup
+        #[arg(long)]
+        ip: Option<String>,
+    },
+    /// Moderate content
+    Moderate { content: String },
+    /// Predict gender from a name
+    Gender { name: String },
+    /// Geolocate an IP address
+    Geolocate { ip: String },
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    let config = credentials::load(cli.profile.as_deref(), cli.config.as_deref())?;
+    let client = Client::new(config)?;
+
+    let result = match cli.command {
+        Resource::Subscribers(command) => subscribers(&client, command).await?,
+        Resource::Events(EventCommand::Track(args)) => track(&client, args).await?,
+        Resource::Commands(command) => commands(&client, command).await?,
+        Resource::Emails(EmailCommand::Send { file }) => {
+            let report = send_emails(&client, read_json(&file)?).await;
+            output::print(&report.to_json(), cli.output);
+            return match report.failure {
+                Some((chunk, err)) => Err(format!(
+                    "chunk {} failed: {}; emails before #{} were already sent",
+                    chunk, err, chunk * MAX_BATCH_SIZE,
+                ).into()),
+                None => Ok(()),
+            };
+        }
+        Resource::Tags(TagCommand::List) => json!(client.get_tags().await?),
+        Resource::Tags(TagCommand::Create { name }) => json!(client.create_tag(&name).await?),
+        Resource::Fields(FieldCommand::List) => json!(client.get_fields().await?),
+        Resource::Fields(FieldCommand::Create { key }) => json!(client.create_field(&key).await?),
+        Resource::Broadcasts(BroadcastCommand::List) => json!(client.get_broadcasts().await?),
+        Resource::Broadcasts(BroadcastCommand::Create { file }) => {
+            let broadcasts: Vec<BroadcastData> = read_json(&file)?;
+            let count = broadcasts.len();
+            client.create_broadcasts(broadcasts).await?;
+            json!({ "created": count })
+        }
+        Resource::Stats(StatsCommand::Site) => json!(client.get_site_stats().await?),
+        Resource::Stats(StatsCommand::Segment { segment_id }) => json!(client.get_segment_stats(&segment_id).await?),
+        Resource::Stats(StatsCommand::Report { report_id }) => json!(client.get_report_stats(&report_id).await?),
+        Resource::Stats(StatsCommand::Export { segments, listen, interval }) => {
+            return export_stats(client, segments, listen, interval).await;
+        }
+        Resource::Experimental(command) => experimental(&client, command).await?,
+    };
+
+    output::print(&result, cli.output);
+    Ok(())
+}
+
+async fn subscribers(client: &Client, command: SubscriberCommand) -> Result<Value, Box<dyn Error>> {
+    Ok(match command {
+        SubscriberCommand::Find { email } => json!(client.find_subscriber(&email).await?),
+        SubscriberCommand::Create { email } => json!(client.create_subscriber(&email).await?),
+        SubscriberCommand::Import { file, chunk_size, concurrency } => {
+            let subscribers: Vec<ImportSubscriberData> = read_json(&file)?;
+            let options = BulkImportOptions::new().chunk_size(chunk_size).concurrency(concurrency);
+            let summary = client.import_subscribers_bulk(subscribers, options).await?;
+
+            let failed_chunks: Vec<Value> = summary.failed_chunks.iter()
+                .map(|chunk| json!({
+                    "chunk": chunk.index,
+                    "subscribers": chunk.subscribers.len(),
+                    "error": chunk.error.to_string(),
+                }))
+                .collect();
+            json!({
+                "succeeded": summary.outcome.succeeded,
+                "failed": summary.outcome.failed,
+                "errors": summary.outcome.errors,
+                "failed_chunks": failed_chunks,
+            })
+        }
+    })
+}
+
+async fn track(client: &Client, args: TrackArgs) -> Result<Value, Box<dyn Error>> {
+    let events = match args.file {
+        Some(file) => read_json(&file)?,
+        None => vec![EventData {
+            event_type: args.event_type.unwrap_or_default(),
+            email: args.email.unwrap_or_default().parse()?,
+            fields: non_empty(parse_pairs(&args.fields)?),
+            details: non_empty(parse_pairs(&args.details)?),
+        }],
+    };
+
+    Ok(json!(client.track_events(events).await?))
+}
+
+async fn commands(client: &Client, command: CommandsCommand) -> Result<Value, Box<dyn Error>> {
+    let email = |address: &str| address.parse::<EmailAddress>();
+
+    let commands = match command {
+        CommandsCommand::AddTag { email: e, tag } => vec![BentoCommand::add_tag(email(&e)?, tag)],
+        CommandsCommand::AddTagViaEvent { email: e, tag } => vec![BentoCommand::add_tag_via_event(email(&e)?, tag)],
+        CommandsCommand::RemoveTag { email: e, tag } => vec![BentoCommand::remove_tag(email(&e)?, tag)],
+        CommandsCommand::AddField { email: e, key, value } => {
+            vec![BentoCommand::add_field(email(&e)?, key, parse_value(&value))]
+        }
+        CommandsCommand::RemoveField { email: e, key } => vec![BentoCommand::remove_field(email(&e)?, key)],
+        CommandsCommand::Subscribe { email: e } => vec![BentoCommand::subscribe(email(&e)?)],
+        CommandsCommand::Unsubscribe { email: e } => vec![BentoCommand::unsubscribe(email(&e)?)],
+        CommandsCommand::ChangeEmail { old, new } => vec![BentoCommand::change_email(email(&old)?, email(&new)?)],
+        CommandsCommand::Run { file } => read_json(&file)?,
+    };
+
+    Ok(json!(client.subscriber_command(commands).await?))
+}
+
+/// Result of sending emails chunk by chunk
+struct EmailSendReport {
+    /// Merged outcome of the chunks that were sent
+    outcome: BatchOutcome,
+    /// Index of the chunk that failed and its error; later chunks were not sent
+    failure: Option<(usize, bento::Error)>,
+}
+
+impl EmailSendReport {
+    fn to_json(&self) -> Value {
+        let mut report = json!(self.outcome);
+        if let Some((chunk, err)) = &self.failure {
+            report["failed_chunk"] = json!(chunk);
+            report["first_unsent_email"] = json!(chunk * MAX_BATCH_SIZE);
+            report["error"] = json!(err.to_string());
+        }
+        report
+    }
+}
+
+/// Sends emails in chunks of `MAX_BATCH_SIZE`, stopping at the first failed chunk
+///
+/// The outcome of the chunks sent before a failure is kept, so the caller can tell
+/// which emails went out instead of resending them.
+async fn send_emails(client: &Client, emails: Vec<EmailData>) -> EmailSendReport {
+    let mut report = EmailSendReport { outcome: BatchOutcome::default(), failure: None };
+
+    for (index, chunk) in emails.chunks(MAX_BATCH_SIZE).enumerate() {
+        let result = match EmailBatch::new(chunk.to_vec()) {
+            Ok(batch) => client.send_emails(batch).await,
+            Err(err) => Err(err),
+        };
+        match result {
+            Ok(outcome) => report.outcome.merge(outcome),
+            Err(err) => {
+                report.failure = Some((index, err));
+                break;
+            }
+        }
+    }
+
+    report
+}
+
+async fn export_stats(
+    client: Client,
+    segments: Vec<String>,
+    listen: SocketAddr,
+    interval: u64,
+) -> Result<(), Box<dyn Error>> {
+    let exporter = StatsExporter::builder(client)
+        .segments(segments)
+        .interval(Duration::from_secs(interval))
+        .build()?;
+    let listener = TcpListener::bind(listen).await
+        .map_err(|e| format!("cannot listen on {}: {}", listen, e))?;
+
+    eprintln!("serving Bento stats on http://{}/metrics", listen);
+    exporter.serve(listener).await?;
+    Ok(())
+}
+
+async fn experimental(client: &Client, command: ExperimentalCommand) -> Result<Value, Box<dyn Error>> {
+    Ok(match command {
+        ExperimentalCommand::Blacklist { domain, ip } => {
+            json!(client.get_blacklist_status(&BlacklistData { domain, ip }).await?)
+        }
+        ExperimentalCommand::Validate { email, name, user_agent, ip } => {
+            let data = ValidationData { email, name, user_agent, ip };
+            json!(client.validate_email(&data).await?)
+        }
+        ExperimentalCommand::Moderate { content } => json!(client.get_content_moderation(&content).await?),
+        ExperimentalCommand::Gender { name } => json!(client.get_gender(&name).await?),
+        ExperimentalCommand::Geolocate { ip } => json!(client.geolocate_ip(&ip).await?),
+    })
+}
+
+/// Reads and parses JSON from a file, or from stdin when the path is `-`.
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, Box<dyn Error>> {
+    let contents = if path == Path::new("-") {
+        let mut buffer = String::new();
+        io::stdin().read_to_string(&mut buffer)?;
+        buffer
+    } else {
+        fs::read_to_string(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?
+    };
+
+    serde_json::from_str(&contents).map_err(|e| format!("invalid JSON in {}: {}", path.display(), e).into())
+}
+
+/// Parses a command-line value as JSON, falling back to a plain string.
+fn parse_value(value: &str) -> Value {
+    serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_string()))
+}
+
+fn parse_pairs(pairs: &[String]) -> Result<HashMap<String, Value>, String> {
+    pairs.iter()
+        .map(|pair| {
+            let (key, value) = pair.split_once('=')
+                .ok_or_else(|| format!("expected KEY=VALUE, got '{}'", pair))?;
+            Ok((key.to_string(), parse_value(value)))
+        })
+        .collect()
+}
+
+fn non_empty(map: HashMap<String, Value>) -> Option<HashMap<String, Value>> {
+    (!map.is_empty()).then_some(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    fn email(subject: &str) -> EmailData {
+        EmailData {
+            to: "test@example.com".parse().unwrap(),
+            from: "sender@example.com".parse().unwrap(),
+            subject: subject.into(),
+            html_body: "<p>Test</p>".into(),
+            transactional: true,
+            personalizations: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_emails_reports_partial_outcome() {
+        let bento = bento::testing::FakeBento::new();
+        let client = bento.client();
+
+        let report = send_emails(&client, (0..=MAX_BATCH_SIZE).map(|_| email("Hi")).collect()).await;
+        assert!(report.failure.is_none());
+        assert_eq!(report.outcome.succeeded as usize, MAX_BATCH_SIZE + 1);
+        assert_eq!(bento.emails().len(), MAX_BATCH_SIZE + 1);
+
+        // The second chunk fails validation after the first was sent
+        let mut emails: Vec<EmailData> = (0..MAX_BATCH_SIZE).map(|_| email("Hi")).collect();
+        emails.push(email(""));
+        emails.push(email("Hi"));
+        let report = send_emails(&client, emails).await;
+
+        assert_eq!(report.outcome.succeeded as usize, MAX_BATCH_SIZE);
+        assert!(matches!(report.failure, Some((1, bento::Error::InvalidRequest(_)))));
+        assert_eq!(bento.emails().len(), 2 * MAX_BATCH_SIZE + 1);
+
+        let json = report.to_json();
+        assert_eq!(json["succeeded"], json!(MAX_BATCH_SIZE));
+        assert_eq!(json["failed_chunk"], json!(1));
+        assert_eq!(json["first_unsent_email"], json!(MAX_BATCH_SIZE));
+    }
+
+    #[test]
+    fn test_parse_pairs() {
+        let pairs = parse_pairs(&["plan=pro".into(), "seats=3".into(), "tags=[\"a\"]".into()]).unwrap();
+        assert_eq!(pairs["plan"], json!("pro"));
+        assert_eq!(pairs["seats"], json!(3));
+        assert_eq!(pairs["tags"], json!(["a"]));
+
+        assert!(parse_pairs(&["missing-separator".into()]).is_err());
+    }
+
+    #[test]
+    fn test_parse_subcommands() {
+        let cli = Cli::try_parse_from([
+            "bento", "--profile", "prod", "-o", "json",
+            "commands", "add-field", "user@example.com", "plan", "pro",
+        ]).unwrap();
+        assert_eq!(cli.profile.as_deref(), Some("prod"));
+        assert_eq!(cli.output, Format::Json);
+        assert!(matches!(cli.command, Resource::Commands(CommandsCommand::AddField { .. })));
+
+        assert!(Cli::try_parse_from(["bento", "events", "track", "--type", "$view"]).is_err());
+        assert!(Cli::try_parse_from(["bento", "events", "track", "--file", "events.json"]).is_ok());
+        assert!(Cli::try_parse_from(["bento", "experimental", "blacklist"]).is_err());
+
+        let cli = Cli::try_parse_from([
+            "bento", "stats", "export", "--segment", "seg_a", "--segment", "seg_b", "--interval", "30",
+        ]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Resource::Stats(StatsCommand::Export { ref segments, interval: 30, .. }) if segments.len() == 2
+        ));
+    }
+}
\ No newline at end of file
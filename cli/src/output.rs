@@ -0,0 +1,158 @@
+//! Printing results as JSON or aligned tables.
+
+use clap::ValueEnum;
+use serde_json::Value;
+
+/// Longest value shown in a table cell before it is truncated
+const MAX_CELL_WIDTH: usize = 60;
+
+/// Output format of command results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Human-readable table
+    Table,
+    /// Pretty-printed JSON
+    Json,
+}
+
+/// Print a result in the given format
+pub fn print(value: &Value, format: Format) {
+    match format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(value).unwrap_or_default()),
+        Format::Table => print!("{}", render_table(value)),
+    }
+}
+
+/// Render a value as a table
+///
+/// Arrays of objects become one row per element, with any `id` column first; a
+/// single object becomes a key/value listing. Nested objects are flattened into
+/// dotted column names, except JSON:API `attributes`, whose keys are used as-is.
+pub fn render_table(value: &Value) -> String {
+    match value {
+        Value::Array(items) if items.is_empty() => "(no results)\n".to_string(),
+        Value::Array(items) => {
+            let rows: Vec<Vec<(String, String)>> = items.iter().map(flatten).collect();
+            let mut columns: Vec<String> = Vec::new();
+            for (key, _) in rows.iter().flatten() {
+                if !columns.contains(key) {
+                    columns.push(key.clone());
+                }
+            }
+            if let Some(position) = columns.iter().position(|c| c == "id") {
+                let id = columns.remove(position);
+                columns.insert(0, id);
+            }
+
+            let cells: Vec<Vec<String>> = rows.iter()
+                .map(|row| {
+                    columns.iter()
+                        .map(|column| row.iter().find(|(key, _)| key == column).map(|(_, v)| v.clone()).unwrap_or_default())
+                        .collect()
+                })
+                .collect();
+            let header = columns.iter().map(|c| c.to_uppercase()).collect();
+
+            align(std::iter::once(header).chain(cells).collect())
+        }
+        Value::Object(_) => {
+            let rows = flatten(value).into_iter().map(|(key, value)| vec![key, value]).collect();
+            align(rows)
+        }
+        other => format!("{}\n", cell(other)),
+    }
+}
+
+fn flatten(value: &Value) -> Vec<(String, String)> {
+    let mut out = Vec::new();
+    flatten_into(value, None, &mut out);
+    out
+}
+
+fn flatten_into(value: &Value, prefix: Option<&str>, out: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                let name = match prefix {
+                    _ if key == "attributes" => prefix.map(str::to_string),
+                    Some(prefix) => Some(format!("{}.{}", prefix, key)),
+                    None => Some(key.clone()),
+                };
+                flatten_into(value, name.as_deref(), out);
+            }
+        }
+        other => out.push((prefix.unwrap_or("value").to_string(), cell(other))),
+    }
+}
+
+fn cell(value: &Value) -> String {
+    let text = match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+
+    if text.chars().count() > MAX_CELL_WIDTH {
+        let truncated: String = text.chars().take(MAX_CELL_WIDTH - 1).collect();
+        format!("{}…", truncated)
+    } else {
+        text
+    }
+}
+
+fn align(rows: Vec<Vec<String>>) -> String {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|i| rows.iter().filter_map(|row| row.get(i)).map(|c| c.chars().count()).max().unwrap_or(0))
+        .collect();
+
+    let mut out = String::new();
+    for row in rows {
+        let line: Vec<String> = row.iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        out.push_str(line.join("  ").trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_render_list() {
+        let value = json!([
+            { "id": "1", "type": "tags", "attributes": { "name": "lead", "site_id": 1 } },
+            { "id": "22", "type": "tags", "attributes": { "name": "customer", "site_id": 1 } }
+        ]);
+
+        assert_eq!(render_table(&value), "\
+ID  NAME      SITE_ID  TYPE
+1   lead      1        tags
+22  customer  1        tags
+");
+    }
+
+    #[test]
+    fn test_render_object() {
+        let value = json!({ "succeeded": 2, "failed": 0, "errors": [], "detail": { "source": null } });
+
+        assert_eq!(render_table(&value), "\
+detail.source
+errors         []
+failed         0
+succeeded      2
+");
+    }
+
+    #[test]
+    fn test_render_scalars() {
+        assert_eq!(render_table(&json!([])), "(no results)\n");
+        assert_eq!(render_table(&json!("done")), "done\n");
+        assert!(render_table(&json!({ "content": "x".repeat(100) })).contains('…'));
+    }
+}
\ No newline at end of file
@@ -1,6 +1,5 @@
 use std::collections::HashMap;
 use dotenv::dotenv;
-use std::env;
 
 use bento::{Client, ConfigBuilder, EventData, BroadcastData, BroadcastType, ContactData, ImportSubscriberData};
 
@@ -10,11 +9,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     dotenv().ok();
 
     // Initialize client
-    let config = ConfigBuilder::new()
-        .publishable_key(&env::var("BENTO_PUBLISHABLE_KEY")?)
-        .secret_key(&env::var("BENTO_SECRET_KEY")?)
-        .site_uuid(&env::var("BENTO_SITE_UUID")?)
-        .build()?;
+    let config = ConfigBuilder::from_env()?.build()?;
 
     let client = Client::new(config)?;
 
@@ -32,7 +27,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     custom_fields.insert("role".to_string(), serde_json::json!("Developer"));
 
     let subscriber = ImportSubscriberData {
-        email: "rust@example.com".to_string(),
+        email: "rust@example.com".parse()?,
         first_name: Some("Rust".to_string()),
         last_name: Some("sdk".to_string()),
         tags: Some("lead,mql".to_string()),
@@ -45,7 +40,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Track event example
     let event = EventData {
         event_type: "$onboarding_complete".to_string(),
-        email: "test@example.com".to_string(),
+        email: "test@example.com".parse()?,
         fields: Some(HashMap::new()),
         details: Some(HashMap::new()),
     };
@@ -63,7 +58,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         broadcast_type: BroadcastType::Plain,
         from: ContactData {
             name: Some("John Doe".to_string()),
-            email: "sender@yourdomain.com".to_string(),
+            email: "sender@yourdomain.com".parse()?,
         },
         inclusive_tags: None,
         exclusive_tags: None,